@@ -0,0 +1,216 @@
+//! Derive macros for [`chance`](https://docs.rs/chance).
+//!
+//! These are re-exported by `chance` when its `derive` feature is enabled, and
+//! should be used through that crate.
+
+#![deny(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input,
+    parse_quote,
+    spanned::Spanned,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Expr,
+    Fields,
+    Lit,
+    LitInt,
+    Result,
+};
+
+/// Derives `chance::Rand` for a struct or enum.
+///
+/// Structs are generated field by field, and enums pick a variant uniformly
+/// before generating its fields. Every type parameter must implement `Rand`.
+///
+/// # Attributes
+///
+/// - `#[chance(range = "0..10")]` on a field generates it via `RandIn` from
+///   the given range instead of via `Rand`. The range may also be written
+///   without quotes. Generation panics if the range is empty.
+///
+/// - `#[chance(weight = 3)]` on a variant makes it 3 times as likely as a
+///   variant with the default weight of 1. A weight of 0 makes the variant
+///   never be generated.
+#[proc_macro_derive(Rand, attributes(chance))]
+pub fn derive_rand(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+
+    let type_params: Vec<_> = input.generics.type_params().map(|p| p.ident.clone()).collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause.predicates.push(parse_quote!(#param: ::chance::Rand));
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            check_no_attrs(&input.attrs, "weight")?;
+            generate_fields(quote!(#name), &data.fields)?
+        },
+        Data::Enum(data) => {
+            let mut total: u64 = 0;
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let weight = variant_weight(&variant.attrs)?;
+                if weight == 0 {
+                    continue;
+                }
+                total = total.checked_add(weight).ok_or_else(|| {
+                    Error::new(variant.span(), "total weight overflows `u64`")
+                })?;
+                let ident = &variant.ident;
+                let value = generate_fields(quote!(#name::#ident), &variant.fields)?;
+                arms.push(quote!(if __index < #total { return Ok(#value); }));
+            }
+            if total == 0 {
+                return Err(Error::new(
+                    name.span(),
+                    "`Rand` cannot be derived for enums without a variant of non-zero weight",
+                ));
+            }
+            quote! {
+                let __index = match <u64 as ::chance::RandIn<_>>::try_rand_in(rng, 0..#total)? {
+                    ::core::option::Option::Some(index) => index,
+                    ::core::option::Option::None => unreachable!(),
+                };
+                #(#arms)*
+                unreachable!()
+            }
+        },
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "`Rand` cannot be derived for unions",
+            ));
+        },
+    };
+
+    let body = match &input.data {
+        Data::Enum(_) => body,
+        _ => quote!(Ok(#body)),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::chance::Rand for #name #ty_generics #where_clause {
+            #[inline]
+            fn rand<__R: ?Sized + ::chance::Rng>(rng: &mut __R) -> Self {
+                match <Self as ::chance::Rand>::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            fn try_rand<__R: ?Sized + ::chance::TryRng>(
+                rng: &mut __R,
+            ) -> ::core::result::Result<Self, __R::Error> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generates an expression that constructs `path` with random `fields`.
+fn generate_fields(path: TokenStream, fields: &Fields) -> Result<TokenStream> {
+    let values = fields
+        .iter()
+        .map(|field| {
+            check_no_attrs(&field.attrs, "weight")?;
+            let ty = &field.ty;
+            let span = field.ty.span();
+            Ok(match field_range(&field.attrs)? {
+                Some(range) => quote_spanned! {span=>
+                    match <#ty as ::chance::RandIn<_>>::try_rand_in(rng, #range)? {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => panic!("empty range: {}", stringify!(#range)),
+                    }
+                },
+                None => quote_spanned! {span=>
+                    <#ty as ::chance::Rand>::try_rand(rng)?
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #values),* })
+        },
+        Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        Fields::Unit => path,
+    })
+}
+
+/// Parses the `#[chance(range = ...)]` attribute of a field.
+fn field_range(attrs: &[Attribute]) -> Result<Option<Expr>> {
+    let mut range = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("chance")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                let expr: Expr = meta.value()?.parse()?;
+                range = Some(match expr {
+                    Expr::Lit(lit) => match lit.lit {
+                        Lit::Str(s) => s.parse()?,
+                        other => return Err(Error::new(other.span(), "expected a range")),
+                    },
+                    expr => expr,
+                });
+                Ok(())
+            } else {
+                Err(meta.error("unknown `chance` attribute"))
+            }
+        })?;
+    }
+    Ok(range)
+}
+
+/// Parses the `#[chance(weight = ...)]` attribute of a variant.
+fn variant_weight(attrs: &[Attribute]) -> Result<u64> {
+    let mut weight = 1;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("chance")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("weight") {
+                weight = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                Ok(())
+            } else if meta.path.is_ident("range") {
+                Err(meta.error("`range` is only allowed on fields"))
+            } else {
+                Err(meta.error("unknown `chance` attribute"))
+            }
+        })?;
+    }
+    Ok(weight)
+}
+
+/// Returns an error if `attrs` contain the `chance` attribute `name`.
+fn check_no_attrs(attrs: &[Attribute], name: &str) -> Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("chance")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(name) {
+                Err(meta.error(format!("`{}` is only allowed on enum variants", name)))
+            } else {
+                // Consume the value so that parsing can continue
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                }
+                Ok(())
+            }
+        })?;
+    }
+    Ok(())
+}
@@ -107,6 +107,12 @@ macro_rules! imp {
             /// Creates a new instance without checking if the `
             #[doc = $instr]
             /// ` instruction is available.
+            ///
+            /// # Safety
+            ///
+            /// The `
+            #[doc = $instr]
+            /// ` instruction must be supported by the current CPU.
             #[inline]
             pub const unsafe fn new_unchecked() -> Self {
                 $t(())
@@ -0,0 +1,80 @@
+//! The Bernoulli distribution.
+
+use crate::prelude::*;
+
+/// The Bernoulli distribution, which samples `true` with probability `p`.
+///
+/// The probability is stored as a 64-bit fixed point value, so sampling costs
+/// a single random `u64` and an integer comparison. Probabilities are exact to
+/// within 2<sup>-64</sup>, and both 0 and 1 are exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bernoulli {
+    /// `p * 2^64`, or `ALWAYS_TRUE` if `p` is 1.
+    p_int: u64,
+}
+
+/// An error returned when creating a [`Bernoulli`](struct.Bernoulli.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BernoulliError {
+    /// The probability is outside of `[0, 1]` or NaN.
+    BadProbability,
+}
+
+/// The value of `p_int` that represents `p == 1`.
+///
+/// Any `p < 1` as an `f64` is at most `1 - 2^-53`, so it never rounds to this.
+const ALWAYS_TRUE: u64 = u64::MAX;
+
+const SCALE: f64 = 2.0 * (1u64 << 63) as f64;
+
+impl Bernoulli {
+    /// Creates a new instance that samples `true` with probability `p`,
+    /// returning an error if `p` is not in `[0, 1]`.
+    #[inline]
+    pub fn new(p: f64) -> Result<Self, BernoulliError> {
+        if !(0.0..1.0).contains(&p) {
+            if p == 1.0 {
+                return Ok(Bernoulli { p_int: ALWAYS_TRUE });
+            }
+            return Err(BernoulliError::BadProbability);
+        }
+        Ok(Bernoulli { p_int: (p * SCALE) as u64 })
+    }
+
+    /// Creates a new instance that samples `true` with probability
+    /// `numerator / denominator`, returning an error if the ratio is not in
+    /// `[0, 1]` or `denominator` is 0.
+    #[inline]
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Result<Self, BernoulliError> {
+        if numerator > denominator || denominator == 0 {
+            return Err(BernoulliError::BadProbability);
+        }
+        if numerator == denominator {
+            return Ok(Bernoulli { p_int: ALWAYS_TRUE });
+        }
+        let p_int = ((numerator as u128) << 64) / denominator as u128;
+        Ok(Bernoulli { p_int: p_int as u64 })
+    }
+
+    /// Returns the probability of sampling `true`, rounded to the nearest
+    /// `f64`.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        if self.p_int == ALWAYS_TRUE {
+            1.0
+        } else {
+            self.p_int as f64 / SCALE
+        }
+    }
+}
+
+impl Distribution<bool> for Bernoulli {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<bool, R::Error> {
+        if self.p_int == ALWAYS_TRUE {
+            return Ok(true);
+        }
+        Ok(rng.try_next_u64()? < self.p_int)
+    }
+}
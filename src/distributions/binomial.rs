@@ -0,0 +1,234 @@
+//! The binomial distribution.
+
+use core::cmp::Ordering;
+use crate::{prelude::*, float::Open01};
+
+/// The binomial distribution `B(n, p)`, which counts the successes in `n`
+/// independent trials that each succeed with probability `p`.
+///
+/// When `n * min(p, 1 - p)` is small, this inverts the cumulative distribution
+/// by sequential search. Otherwise, it uses the BTPE algorithm described in
+/// "Binomial Random Variate Generation" by Voratas Kachitvichyanukul and Bruce
+/// W. Schmeiser, whose expected cost does not depend on `n`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+/// An error returned when creating a [`Binomial`](struct.Binomial.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BinomialError {
+    /// The probability is outside of `[0, 1]` or NaN.
+    BadProbability,
+}
+
+/// The mean below which inversion is faster than BTPE.
+const INVERSION_THRESHOLD: f64 = 10.0;
+
+/// The largest result inversion tries before starting over, which only happens
+/// when rounding leaves some probability mass unaccounted for.
+const INVERSION_MAX_X: u64 = 110;
+
+/// The distance from the mode within which BTPE evaluates the density ratio
+/// directly rather than through Stirling's approximation.
+const SQUEEZE_THRESHOLD: i64 = 20;
+
+impl Binomial {
+    /// Creates a new instance with `n` trials that each succeed with
+    /// probability `p`, returning an error if `p` is not in `[0, 1]`.
+    #[inline]
+    pub fn new(n: u64, p: f64) -> Result<Self, BinomialError> {
+        if (0.0..=1.0).contains(&p) {
+            Ok(Binomial { n, p })
+        } else {
+            Err(BinomialError::BadProbability)
+        }
+    }
+
+    /// Returns the number of trials.
+    #[inline]
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the probability of success for each trial.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl Distribution<u64> for Binomial {
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<u64, R::Error> {
+        if self.p == 0.0 {
+            return Ok(0);
+        }
+        if self.p == 1.0 {
+            return Ok(self.n);
+        }
+
+        // Sample the number of the rarer outcome
+        let flipped = self.p > 0.5;
+        let p = if flipped { 1.0 - self.p } else { self.p };
+        let q = 1.0 - p;
+        let n = self.n as f64;
+
+        let result = if n * p < INVERSION_THRESHOLD {
+            try_inversion(rng, self.n, p, q)?
+        } else {
+            try_btpe(rng, self.n, p, q)?
+        };
+        Ok(if flipped { self.n - result } else { result })
+    }
+}
+
+fn try_inversion<R: ?Sized + TryRng>(
+    rng: &mut R,
+    n: u64,
+    p: f64,
+    q: f64,
+) -> Result<u64, R::Error> {
+    let s = p / q;
+    let a = (n as f64 + 1.0) * s;
+    let q_n = q.powf(n as f64);
+
+    'restart: loop {
+        let mut r = q_n;
+        let mut u = f64::try_rand(rng)?;
+        let mut x = 0;
+        while u > r {
+            u -= r;
+            x += 1;
+            if x > INVERSION_MAX_X || x > n {
+                continue 'restart;
+            }
+            // f(x) = f(x - 1) * (n - x + 1) / x * p / q
+            r *= a / x as f64 - s;
+        }
+        return Ok(x);
+    }
+}
+
+fn try_btpe<R: ?Sized + TryRng>(
+    rng: &mut R,
+    n: u64,
+    p: f64,
+    q: f64,
+) -> Result<u64, R::Error> {
+    #[inline]
+    fn lambda(a: f64) -> f64 {
+        a * (1.0 + 0.5 * a)
+    }
+
+    // The error of Stirling's approximation of `ln((a - 1)!)`
+    #[inline]
+    fn stirling(a: f64) -> f64 {
+        let a2 = a * a;
+        (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / a2) / a2) / a2) / a2) / a / 166320.0
+    }
+
+    let n = n as f64;
+    let np = n * p;
+    let npq = np * q;
+    let f_m = np + p;
+    let m = f_m as i64;
+
+    // The triangle in the middle, the parallelograms beside it and the
+    // exponential tails, with the cumulative area up to the end of each
+    let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+    let x_m = m as f64 + 0.5;
+    let x_l = x_m - p1;
+    let x_r = x_m + p1;
+    let c = 0.134 + 20.5 / (15.3 + m as f64);
+    let p2 = p1 * (1.0 + 2.0 * c);
+    let lambda_l = lambda((f_m - x_l) / (f_m - x_l * p));
+    let lambda_r = lambda((x_r - f_m) / (x_r * q));
+    let p3 = p2 + c / lambda_l;
+    let p4 = p3 + c / lambda_r;
+
+    loop {
+        let u = p4 * f64::try_rand(rng)?;
+        let mut v = Open01::<f64>::try_rand(rng)?.0;
+
+        let y = if u <= p1 {
+            // The triangle always accepts
+            return Ok((x_m - p1 * v + u) as u64);
+        } else if u <= p2 {
+            let x = x_l + (u - p1) / c;
+            v = v * c + 1.0 - (x - x_m).abs() / p1;
+            if v > 1.0 {
+                continue;
+            }
+            x as i64
+        } else if u <= p3 {
+            let y = (x_l + v.ln() / lambda_l) as i64;
+            if y < 0 {
+                continue;
+            }
+            v *= (u - p2) * lambda_l;
+            y
+        } else {
+            let y = (x_r - v.ln() / lambda_r) as i64;
+            if y as f64 > n {
+                continue;
+            }
+            v *= (u - p3) * lambda_r;
+            y
+        };
+
+        let k = (y - m).abs();
+        if k <= SQUEEZE_THRESHOLD || k as f64 >= npq / 2.0 - 1.0 {
+            // Evaluate f(y) / f(m) with the recurrence between neighbors
+            let s = p / q;
+            let a = s * (n + 1.0);
+            let mut f = 1.0;
+            match m.cmp(&y) {
+                Ordering::Less => {
+                    for i in (m + 1)..=y {
+                        f *= a / i as f64 - s;
+                    }
+                },
+                Ordering::Greater => {
+                    for i in (y + 1)..=m {
+                        f /= a / i as f64 - s;
+                    }
+                },
+                Ordering::Equal => {},
+            }
+            if v <= f {
+                return Ok(y as u64);
+            }
+            continue;
+        }
+
+        // Squeeze using bounds on ln(f(y) / f(m))
+        let k = k as f64;
+        let rho = (k / npq) * ((k * (k / 3.0 + 0.625) + 1.0 / 6.0) / npq + 0.5);
+        let t = -0.5 * k * k / npq;
+        let alpha = v.ln();
+        if alpha < t - rho {
+            return Ok(y as u64);
+        }
+        if alpha > t + rho {
+            continue;
+        }
+
+        // Compare against ln(f(y) / f(m)) from Stirling's formula
+        let x1 = (y + 1) as f64;
+        let f1 = (m + 1) as f64;
+        let z = n + 1.0 - m as f64;
+        let w = n - y as f64 + 1.0;
+        let bound = x_m * (f1 / x1).ln()
+            + (n - m as f64 + 0.5) * (z / w).ln()
+            + (y - m) as f64 * (w * p / (x1 * q)).ln()
+            + stirling(f1)
+            + stirling(z)
+            - stirling(x1)
+            - stirling(w);
+        if alpha <= bound {
+            return Ok(y as u64);
+        }
+    }
+}
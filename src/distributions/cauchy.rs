@@ -0,0 +1,83 @@
+//! The Cauchy distribution.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The Cauchy distribution `Cauchy(median, scale)`.
+///
+/// This inverts the cumulative distribution as `median + scale * tan(π (u -
+/// 1/2))`. The distribution has no mean, so sample averages never converge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cauchy<F = f64> {
+    median: F,
+    scale: F,
+}
+
+/// An error returned when creating a [`Cauchy`](struct.Cauchy.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CauchyError {
+    /// The median is infinite or NaN.
+    MedianNotFinite,
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+}
+
+impl<F: Float> Cauchy<F> {
+    /// Creates a new instance with `median` and `scale`, returning an error if
+    /// either is not finite or if `scale` is not positive.
+    #[inline]
+    pub fn new(median: F, scale: F) -> Result<Self, CauchyError> {
+        if !median.is_finite() {
+            return Err(CauchyError::MedianNotFinite);
+        }
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(CauchyError::BadScale);
+        }
+        Ok(Cauchy { median, scale })
+    }
+
+    /// Returns the median of the distribution.
+    #[inline]
+    pub fn median(&self) -> F {
+        self.median
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+}
+
+impl<F: Float> Distribution<F> for Cauchy<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let u = F::try_open01(rng)?;
+        Ok(self.median + self.scale * (F::PI * (u - F::from_f64(0.5))).tan())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, rng};
+
+    #[test]
+    fn cauchy_fits_cdf() {
+        let cauchy = Cauchy::new(1.0, 2.0).unwrap();
+        let mut rng = rng(1);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| cauchy.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| 0.5 + ((x - 1.0) / 2.0).atan() / core::f64::consts::PI);
+
+        let cauchy = Cauchy::<f32>::new(-3.0, 0.5).unwrap();
+        let mut samples: Vec<f64> = (0..100_000).map(|_| cauchy.sample(&mut rng) as f64).collect();
+        assert_ks(&mut samples, |x| 0.5 + ((x + 3.0) / 0.5).atan() / core::f64::consts::PI);
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Cauchy::new(f64::NAN, 1.0), Err(CauchyError::MedianNotFinite));
+        assert_eq!(Cauchy::new(0.0, 0.0), Err(CauchyError::BadScale));
+        assert_eq!(Cauchy::new(0.0, f64::INFINITY), Err(CauchyError::BadScale));
+    }
+}
@@ -0,0 +1,119 @@
+//! The exponential distribution.
+
+use crate::{
+    prelude::*,
+    distributions::{Float, ziggurat::{self, EXP_F, EXP_R, EXP_X}},
+    float::Open01,
+};
+
+/// The exponential distribution `Exp(1)`.
+///
+/// This uses the Ziggurat method with precomputed tables, which usually costs
+/// a single random `u64` per sample. Values for `f32` are sampled as `f64` and
+/// then rounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Exp1;
+
+impl Distribution<f64> for Exp1 {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<f64, R::Error> {
+        #[inline]
+        fn pdf(x: f64) -> f64 {
+            (-x).exp()
+        }
+
+        // The tail is memoryless, so it's just a shifted copy of the whole
+        #[inline]
+        fn tail<R: ?Sized + TryRng>(rng: &mut R, _u: f64) -> Result<f64, R::Error> {
+            Ok(EXP_R - Open01::<f64>::try_rand(rng)?.0.ln())
+        }
+
+        ziggurat::try_sample(rng, false, &EXP_X, &EXP_F, pdf, tail)
+    }
+}
+
+impl Distribution<f32> for Exp1 {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<f32, R::Error> {
+        Distribution::<f64>::try_sample(self, rng).map(|x| x as f32)
+    }
+}
+
+/// The exponential distribution `Exp(lambda)`.
+///
+/// This scales [`Exp1`](struct.Exp1.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exp<F = f64> {
+    lambda: F,
+    lambda_inverse: F,
+}
+
+/// An error returned when creating an [`Exp`](struct.Exp.html) with invalid
+/// parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExpError {
+    /// The rate is zero, negative or NaN.
+    BadLambda,
+}
+
+impl<F: Float> Exp<F> {
+    /// Creates a new instance with the rate `lambda`, returning an error if
+    /// `lambda` is not positive.
+    ///
+    /// An infinite `lambda` always samples 0.
+    #[inline]
+    pub fn new(lambda: F) -> Result<Self, ExpError> {
+        if lambda > F::ZERO {
+            Ok(Exp { lambda, lambda_inverse: F::ONE / lambda })
+        } else {
+            Err(ExpError::BadLambda)
+        }
+    }
+
+    /// Returns the rate of the distribution.
+    #[inline]
+    pub fn lambda(&self) -> F {
+        self.lambda
+    }
+}
+
+impl<F: Float> Distribution<F> for Exp<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        Ok(F::try_exp1(rng)? * self.lambda_inverse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, assert_moments, rng};
+
+    #[test]
+    fn exp_moments() {
+        let exp = Exp::new(2.0).unwrap();
+        let mut rng = rng(1);
+        let samples: Vec<f64> = (0..100_000).map(|_| exp.sample(&mut rng)).collect();
+        assert_moments(&samples, 0.5, 0.25, 9.0);
+
+        let exp = Exp::<f32>::new(0.5).unwrap();
+        let samples: Vec<f64> = (0..100_000).map(|_| exp.sample(&mut rng) as f64).collect();
+        assert_moments(&samples, 2.0, 4.0, 9.0);
+    }
+
+    #[test]
+    fn exp_fits_cdf() {
+        let exp = Exp::new(2.0).unwrap();
+        let mut rng = rng(2);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| exp.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| 1.0 - (-2.0 * x).exp());
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Exp::new(0.0), Err(ExpError::BadLambda));
+        assert_eq!(Exp::new(-1.0), Err(ExpError::BadLambda));
+        assert_eq!(Exp::new(f64::NAN), Err(ExpError::BadLambda));
+        assert_eq!(Exp::new(f64::INFINITY).unwrap().sample(&mut rng(3)), 0.0);
+    }
+}
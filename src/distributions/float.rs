@@ -0,0 +1,144 @@
+//! The float types that continuous distributions are generic over.
+
+use core::{fmt::Debug, ops::{Add, Div, Mul, Neg, Sub}};
+use crate::{
+    prelude::*,
+    distributions::{Exp1, StandardNormal},
+    float::{Open01, OpenClosed01},
+};
+
+/// A float type that continuous distributions can be sampled in.
+///
+/// This is implemented for `f32` and `f64`, and cannot be implemented outside
+/// of this crate.
+pub trait Float: Copy + private::Float {}
+
+pub(crate) mod private {
+    use super::*;
+
+    /// The operations behind [`Float`](../trait.Float.html), kept out of the
+    /// public API.
+    pub trait Float: Copy + Debug + PartialOrd
+        + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+        + Div<Output = Self> + Neg<Output = Self>
+    {
+        /// The value 0.
+        const ZERO: Self;
+
+        /// The value 1.
+        const ONE: Self;
+
+        /// Archimedes' constant `π`.
+        const PI: Self;
+
+        /// Converts a constant to `Self`, rounding if necessary.
+        fn from_f64(x: f64) -> Self;
+
+        /// Returns whether `self` is neither infinite nor NaN.
+        fn is_finite(self) -> bool;
+
+        /// Returns whether `self` is NaN.
+        fn is_nan(self) -> bool;
+
+        /// Returns the square root of `self`.
+        fn sqrt(self) -> Self;
+
+        /// Returns the natural logarithm of `self`.
+        fn ln(self) -> Self;
+
+        /// Returns `e^self`.
+        fn exp(self) -> Self;
+
+        /// Returns the tangent of `self` in radians.
+        fn tan(self) -> Self;
+
+        /// Returns `self^n`.
+        fn powf(self, n: Self) -> Self;
+
+        /// Samples from `(0, 1)`.
+        fn try_open01<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error>;
+
+        /// Samples from `(0, 1]`.
+        fn try_open_closed01<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error>;
+
+        /// Samples from [`StandardNormal`](../struct.StandardNormal.html).
+        fn try_standard_normal<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error>;
+
+        /// Samples from [`Exp1`](../struct.Exp1.html).
+        fn try_exp1<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error>;
+    }
+}
+
+macro_rules! imp {
+    ($($float:ident),+) => { $(
+        impl Float for $float {}
+
+        impl private::Float for $float {
+            const ZERO: $float = 0.0;
+            const ONE: $float = 1.0;
+            const PI: $float = core::$float::consts::PI;
+
+            #[inline]
+            fn from_f64(x: f64) -> $float {
+                x as $float
+            }
+
+            #[inline]
+            fn is_finite(self) -> bool {
+                $float::is_finite(self)
+            }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                $float::is_nan(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> $float {
+                $float::sqrt(self)
+            }
+
+            #[inline]
+            fn ln(self) -> $float {
+                $float::ln(self)
+            }
+
+            #[inline]
+            fn exp(self) -> $float {
+                $float::exp(self)
+            }
+
+            #[inline]
+            fn tan(self) -> $float {
+                $float::tan(self)
+            }
+
+            #[inline]
+            fn powf(self, n: $float) -> $float {
+                $float::powf(self, n)
+            }
+
+            #[inline]
+            fn try_open01<R: ?Sized + TryRng>(rng: &mut R) -> Result<$float, R::Error> {
+                Open01::<$float>::try_rand(rng).map(|Open01(x)| x)
+            }
+
+            #[inline]
+            fn try_open_closed01<R: ?Sized + TryRng>(rng: &mut R) -> Result<$float, R::Error> {
+                OpenClosed01::<$float>::try_rand(rng).map(|OpenClosed01(x)| x)
+            }
+
+            #[inline]
+            fn try_standard_normal<R: ?Sized + TryRng>(rng: &mut R) -> Result<$float, R::Error> {
+                StandardNormal.try_sample(rng)
+            }
+
+            #[inline]
+            fn try_exp1<R: ?Sized + TryRng>(rng: &mut R) -> Result<$float, R::Error> {
+                Exp1.try_sample(rng)
+            }
+        }
+    )+ }
+}
+
+imp!(f32, f64);
@@ -0,0 +1,95 @@
+//! The Fréchet distribution.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The Fréchet distribution `Fréchet(location, scale, shape)`.
+///
+/// This inverts the cumulative distribution as `location + scale * (-ln
+/// u)^(-1 / shape)`, so every value is greater than `location`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frechet<F = f64> {
+    location: F,
+    scale: F,
+    shape: F,
+}
+
+/// An error returned when creating a [`Frechet`](struct.Frechet.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrechetError {
+    /// The location is infinite or NaN.
+    LocationNotFinite,
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+    /// The shape is zero, negative, infinite or NaN.
+    BadShape,
+}
+
+impl<F: Float> Frechet<F> {
+    /// Creates a new instance with `location`, `scale` and `shape`, returning
+    /// an error if any is not finite or if `scale` or `shape` is not positive.
+    #[inline]
+    pub fn new(location: F, scale: F, shape: F) -> Result<Self, FrechetError> {
+        if !location.is_finite() {
+            return Err(FrechetError::LocationNotFinite);
+        }
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(FrechetError::BadScale);
+        }
+        if !(shape > F::ZERO && shape.is_finite()) {
+            return Err(FrechetError::BadShape);
+        }
+        Ok(Frechet { location, scale, shape })
+    }
+
+    /// Returns the location of the distribution.
+    #[inline]
+    pub fn location(&self) -> F {
+        self.location
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+
+    /// Returns the shape of the distribution.
+    #[inline]
+    pub fn shape(&self) -> F {
+        self.shape
+    }
+}
+
+impl<F: Float> Distribution<F> for Frechet<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let u = F::try_open01(rng)?;
+        Ok(self.location + self.scale * (-u.ln()).powf(-F::ONE / self.shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, rng};
+
+    #[test]
+    fn frechet_fits_cdf() {
+        let frechet = Frechet::new(1.0, 2.0, 3.0).unwrap();
+        let mut rng = rng(1);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| frechet.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| (-((x - 1.0) / 2.0).powf(-3.0)).exp());
+
+        let frechet = Frechet::<f32>::new(0.0, 1.0, 0.5).unwrap();
+        let mut samples: Vec<f64> = (0..100_000).map(|_| frechet.sample(&mut rng) as f64).collect();
+        assert_ks(&mut samples, |x| (-x.powf(-0.5)).exp());
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Frechet::new(f64::NAN, 1.0, 1.0), Err(FrechetError::LocationNotFinite));
+        assert_eq!(Frechet::new(0.0, -1.0, 1.0), Err(FrechetError::BadScale));
+        assert_eq!(Frechet::new(0.0, 1.0, 0.0), Err(FrechetError::BadShape));
+    }
+}
@@ -0,0 +1,320 @@
+//! The gamma distribution and the distributions derived from it.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The gamma distribution `Γ(shape, scale)`.
+///
+/// This uses the method described in "A Simple Method for Generating Gamma
+/// Variables" by George Marsaglia and Wai Wan Tsang. A `shape` below 1 is
+/// handled by sampling `Γ(shape + 1)` and multiplying by `U^(1 / shape)`, and
+/// a `shape` of exactly 1 samples [`Exp1`](struct.Exp1.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamma<F = f64> {
+    shape: F,
+    scale: F,
+    repr: GammaRepr<F>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GammaRepr<F> {
+    One,
+    Small { inv_shape: F, large: GammaLarge<F> },
+    Large(GammaLarge<F>),
+}
+
+/// The Marsaglia–Tsang constants for `Γ(shape, 1)` where `shape >= 1`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct GammaLarge<F> {
+    d: F,
+    c: F,
+}
+
+/// An error returned when creating a [`Gamma`](struct.Gamma.html) with invalid
+/// parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GammaError {
+    /// The shape is zero, negative, infinite or NaN.
+    BadShape,
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+}
+
+/// The beta distribution `Beta(alpha, beta)`.
+///
+/// This samples `X / (X + Y)` where `X` and `Y` are sampled from `Γ(alpha, 1)`
+/// and `Γ(beta, 1)`. If either parameter is below 1, the ratio is computed
+/// from `ln X` and `ln Y` instead, since `X` and `Y` can both underflow to zero
+/// for tiny parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Beta<F = f64> {
+    alpha: Gamma<F>,
+    beta: Gamma<F>,
+}
+
+/// An error returned when creating a [`Beta`](struct.Beta.html) with invalid
+/// parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BetaError {
+    /// Alpha is zero, negative, infinite or NaN.
+    BadAlpha,
+    /// Beta is zero, negative, infinite or NaN.
+    BadBeta,
+}
+
+/// The chi-squared distribution `χ²(k)`.
+///
+/// This samples `Γ(k / 2, 2)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChiSquared<F = f64> {
+    k: F,
+    gamma: Gamma<F>,
+}
+
+/// An error returned when creating a [`ChiSquared`](struct.ChiSquared.html)
+/// with invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChiSquaredError {
+    /// The degrees of freedom are zero, negative, infinite or NaN.
+    BadDegreesOfFreedom,
+}
+
+impl<F: Float> GammaLarge<F> {
+    #[inline]
+    fn new(shape: F) -> Self {
+        let d = shape - F::from_f64(1.0 / 3.0);
+        GammaLarge { d, c: F::ONE / (F::from_f64(9.0) * d).sqrt() }
+    }
+
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        loop {
+            let x = F::try_standard_normal(rng)?;
+            let v = F::ONE + self.c * x;
+            if v <= F::ZERO {
+                continue;
+            }
+            let v = v * v * v;
+            let u = F::try_open01(rng)?;
+
+            // Cheap squeeze before the exact test
+            let x_sq = x * x;
+            if u < F::ONE - F::from_f64(0.0331) * x_sq * x_sq
+                || u.ln() < F::from_f64(0.5) * x_sq + self.d * (F::ONE - v + v.ln())
+            {
+                return Ok(self.d * v);
+            }
+        }
+    }
+}
+
+impl<F: Float> Gamma<F> {
+    /// Creates a new instance with `shape` and `scale`, returning an error if
+    /// either is not positive and finite.
+    pub fn new(shape: F, scale: F) -> Result<Self, GammaError> {
+        if !(shape > F::ZERO && shape.is_finite()) {
+            return Err(GammaError::BadShape);
+        }
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(GammaError::BadScale);
+        }
+        let repr = if shape == F::ONE {
+            GammaRepr::One
+        } else if shape < F::ONE {
+            GammaRepr::Small {
+                inv_shape: F::ONE / shape,
+                large: GammaLarge::new(shape + F::ONE),
+            }
+        } else {
+            GammaRepr::Large(GammaLarge::new(shape))
+        };
+        Ok(Gamma { shape, scale, repr })
+    }
+
+    /// Samples the logarithm of a value from `Γ(shape, 1)`, which stays finite
+    /// even when the value itself underflows to zero.
+    fn try_sample_ln_unscaled<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        Ok(match self.repr {
+            GammaRepr::One => F::try_exp1(rng)?.ln(),
+            GammaRepr::Small { inv_shape, large } => {
+                let u = F::try_open01(rng)?;
+                large.try_sample(rng)?.ln() + u.ln() * inv_shape
+            },
+            GammaRepr::Large(large) => large.try_sample(rng)?.ln(),
+        })
+    }
+
+    /// Returns the shape of the distribution.
+    #[inline]
+    pub fn shape(&self) -> F {
+        self.shape
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+}
+
+impl<F: Float> Distribution<F> for Gamma<F> {
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let x = match self.repr {
+            GammaRepr::One => F::try_exp1(rng)?,
+            GammaRepr::Small { inv_shape, large } => {
+                let u = F::try_open01(rng)?;
+                large.try_sample(rng)? * u.powf(inv_shape)
+            },
+            GammaRepr::Large(large) => large.try_sample(rng)?,
+        };
+        Ok(x * self.scale)
+    }
+}
+
+impl<F: Float> Beta<F> {
+    /// Creates a new instance with `alpha` and `beta`, returning an error if
+    /// either is not positive and finite, or is so small that its reciprocal
+    /// overflows.
+    pub fn new(alpha: F, beta: F) -> Result<Self, BetaError> {
+        fn gamma<F: Float>(shape: F) -> Option<Gamma<F>> {
+            if (F::ONE / shape).is_finite() {
+                Gamma::new(shape, F::ONE).ok()
+            } else {
+                None
+            }
+        }
+
+        Ok(Beta {
+            alpha: gamma(alpha).ok_or(BetaError::BadAlpha)?,
+            beta: gamma(beta).ok_or(BetaError::BadBeta)?,
+        })
+    }
+
+    /// Returns the alpha parameter of the distribution.
+    #[inline]
+    pub fn alpha(&self) -> F {
+        self.alpha.shape
+    }
+
+    /// Returns the beta parameter of the distribution.
+    #[inline]
+    pub fn beta(&self) -> F {
+        self.beta.shape
+    }
+}
+
+impl<F: Float> Distribution<F> for Beta<F> {
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        if self.alpha.shape >= F::ONE && self.beta.shape >= F::ONE {
+            // Neither sample can be zero
+            let x = self.alpha.try_sample(rng)?;
+            let y = self.beta.try_sample(rng)?;
+            return Ok(x / (x + y));
+        }
+
+        loop {
+            // `X / (X + Y) = 1 / (1 + Y / X)`
+            let ln_x = self.alpha.try_sample_ln_unscaled(rng)?;
+            let ln_y = self.beta.try_sample_ln_unscaled(rng)?;
+            let ratio = (ln_y - ln_x).exp();
+
+            // Only if both samples were exactly zero
+            if !ratio.is_nan() {
+                return Ok(F::ONE / (F::ONE + ratio));
+            }
+        }
+    }
+}
+
+impl<F: Float> ChiSquared<F> {
+    /// Creates a new instance with `k` degrees of freedom, returning an error
+    /// if `k` is not positive and finite.
+    pub fn new(k: F) -> Result<Self, ChiSquaredError> {
+        match Gamma::new(F::from_f64(0.5) * k, F::from_f64(2.0)) {
+            Ok(gamma) => Ok(ChiSquared { k, gamma }),
+            Err(_) => Err(ChiSquaredError::BadDegreesOfFreedom),
+        }
+    }
+
+    /// Returns the degrees of freedom of the distribution.
+    #[inline]
+    pub fn degrees_of_freedom(&self) -> F {
+        self.k
+    }
+}
+
+impl<F: Float> Distribution<F> for ChiSquared<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        self.gamma.try_sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_moments, rng};
+
+    fn samples<D: Distribution<f64>>(distribution: D, seed: u64) -> Vec<f64> {
+        let mut rng = rng(seed);
+        (0..100_000).map(|_| distribution.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn gamma_moments() {
+        // Covers the small, exponential and large shape cases
+        for (i, &shape) in [0.5, 1.0, 5.0].iter().enumerate() {
+            let samples = samples(Gamma::new(shape, 2.0).unwrap(), i as u64);
+            assert_moments(&samples, shape * 2.0, shape * 4.0, 3.0 + 6.0 / shape);
+        }
+
+        let gamma = Gamma::<f32>::new(3.0, 0.5).unwrap();
+        let mut rng = rng(3);
+        let samples: Vec<f64> = (0..100_000).map(|_| gamma.sample(&mut rng) as f64).collect();
+        assert_moments(&samples, 1.5, 0.75, 5.0);
+    }
+
+    #[test]
+    fn beta_moments() {
+        let samples = samples(Beta::new(2.0, 5.0).unwrap(), 4);
+        assert!(samples.iter().all(|&x| (0.0..=1.0).contains(&x)));
+        assert_moments(&samples, 2.0 / 7.0, 10.0 / (49.0 * 8.0), 2.88);
+    }
+
+    #[test]
+    fn beta_with_tiny_parameters() {
+        // Nearly all of the mass is next to 0 and 1, split evenly between them
+        let samples = samples(Beta::new(1e-20, 1e-20).unwrap(), 6);
+        assert!(samples.iter().all(|&x| (0.0..=1.0).contains(&x)));
+        let high = samples.iter().filter(|&&x| x > 0.5).count() as f64 / samples.len() as f64;
+        assert!((high - 0.5).abs() < 5.0 * (0.25 / samples.len() as f64).sqrt());
+
+        let beta = Beta::<f32>::new(1e-20, 1e-3).unwrap();
+        let mut rng = rng(7);
+        for _ in 0..10_000 {
+            let x = beta.sample(&mut rng);
+            assert!((0.0..=1.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn beta_moments_in_log_space() {
+        let samples = samples(Beta::new(0.5, 2.0).unwrap(), 8);
+        assert_moments(&samples, 0.2, 1.0 / 21.875, 3.0 + 6.0 * 3.375 / 24.75);
+    }
+
+    #[test]
+    fn chi_squared_moments() {
+        let samples = samples(ChiSquared::new(3.0).unwrap(), 5);
+        assert_moments(&samples, 3.0, 6.0, 7.0);
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Gamma::new(0.0, 1.0), Err(GammaError::BadShape));
+        assert_eq!(Gamma::new(1.0, f64::INFINITY), Err(GammaError::BadScale));
+        assert_eq!(Beta::new(f64::NAN, 1.0), Err(BetaError::BadAlpha));
+        assert_eq!(Beta::new(1.0, -1.0), Err(BetaError::BadBeta));
+        assert_eq!(Beta::new(1e-310, 1.0), Err(BetaError::BadAlpha));
+        assert_eq!(ChiSquared::new(0.0), Err(ChiSquaredError::BadDegreesOfFreedom));
+    }
+}
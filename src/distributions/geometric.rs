@@ -0,0 +1,54 @@
+//! The geometric distribution.
+
+use crate::{prelude::*, float::Open01};
+
+/// The geometric distribution `Geo(p)`, which counts the failures before the
+/// first success in independent trials that each succeed with probability `p`.
+///
+/// This inverts the cumulative distribution, which costs one random `f64` and
+/// two logarithms per sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Geometric {
+    p: f64,
+    /// `1 / ln(1 - p)`, which is negative.
+    inv_ln_q: f64,
+}
+
+/// An error returned when creating a [`Geometric`](struct.Geometric.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GeometricError {
+    /// The probability is outside of `(0, 1]` or NaN.
+    BadProbability,
+}
+
+impl Geometric {
+    /// Creates a new instance where each trial succeeds with probability `p`,
+    /// returning an error if `p` is not in `(0, 1]`.
+    #[inline]
+    pub fn new(p: f64) -> Result<Self, GeometricError> {
+        if p > 0.0 && p <= 1.0 {
+            Ok(Geometric { p, inv_ln_q: 1.0 / (-p).ln_1p() })
+        } else {
+            Err(GeometricError::BadProbability)
+        }
+    }
+
+    /// Returns the probability of success for each trial.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<u64, R::Error> {
+        if self.p == 1.0 {
+            return Ok(0);
+        }
+        // P(X >= k) = (1 - p)^k, so this saturates for results beyond `u64`
+        let u = Open01::<f64>::try_rand(rng)?.0;
+        Ok((u.ln() * self.inv_ln_q).floor() as u64)
+    }
+}
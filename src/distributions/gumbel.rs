@@ -0,0 +1,82 @@
+//! The Gumbel distribution.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The Gumbel distribution `Gumbel(location, scale)`.
+///
+/// This inverts the cumulative distribution as `location - scale * ln(-ln u)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gumbel<F = f64> {
+    location: F,
+    scale: F,
+}
+
+/// An error returned when creating a [`Gumbel`](struct.Gumbel.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GumbelError {
+    /// The location is infinite or NaN.
+    LocationNotFinite,
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+}
+
+impl<F: Float> Gumbel<F> {
+    /// Creates a new instance with `location` and `scale`, returning an error
+    /// if either is not finite or if `scale` is not positive.
+    #[inline]
+    pub fn new(location: F, scale: F) -> Result<Self, GumbelError> {
+        if !location.is_finite() {
+            return Err(GumbelError::LocationNotFinite);
+        }
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(GumbelError::BadScale);
+        }
+        Ok(Gumbel { location, scale })
+    }
+
+    /// Returns the location of the distribution.
+    #[inline]
+    pub fn location(&self) -> F {
+        self.location
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+}
+
+impl<F: Float> Distribution<F> for Gumbel<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let u = F::try_open01(rng)?;
+        Ok(self.location - self.scale * (-u.ln()).ln())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, rng};
+
+    #[test]
+    fn gumbel_fits_cdf() {
+        let gumbel = Gumbel::new(1.0, 2.0).unwrap();
+        let mut rng = rng(1);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| gumbel.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| (-(-(x - 1.0) / 2.0).exp()).exp());
+
+        let gumbel = Gumbel::<f32>::new(-1.0, 0.5).unwrap();
+        let mut samples: Vec<f64> = (0..100_000).map(|_| gumbel.sample(&mut rng) as f64).collect();
+        assert_ks(&mut samples, |x| (-(-(x + 1.0) / 0.5).exp()).exp());
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Gumbel::new(f64::INFINITY, 1.0), Err(GumbelError::LocationNotFinite));
+        assert_eq!(Gumbel::new(0.0, 0.0), Err(GumbelError::BadScale));
+        assert_eq!(Gumbel::new(0.0, f64::NAN), Err(GumbelError::BadScale));
+    }
+}
@@ -0,0 +1,254 @@
+//! The hypergeometric distribution.
+
+use crate::{prelude::*, distributions::math::ln_factorial, float::Open01};
+
+/// The hypergeometric distribution, which counts the successes in `draws`
+/// values drawn without replacement from `total` values, of which `successes`
+/// are successes.
+///
+/// The parameters are first transformed so that fewer than half of the values
+/// are successes and fewer than half are drawn. When the mode is close to the
+/// smallest possible result, this inverts the cumulative distribution by
+/// sequential search. Otherwise, it uses the H2PE algorithm described in
+/// "Computer Generation of Hypergeometric Random Variates" by Voratas
+/// Kachitvichyanukul and Bruce W. Schmeiser, whose expected cost does not
+/// depend on the parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hypergeometric {
+    total: u64,
+    successes: u64,
+    draws: u64,
+    /// The transformed number of successes.
+    n1: u64,
+    /// The transformed number of failures.
+    n2: u64,
+    /// The transformed number of draws.
+    k: u64,
+    /// Whether `n1` counts failures instead of successes.
+    swapped: bool,
+    /// Whether `k` counts the values left behind instead of drawn.
+    complemented: bool,
+    /// The smallest and largest possible transformed results.
+    x_min: u64,
+    x_max: u64,
+    repr: HypergeometricRepr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum HypergeometricRepr {
+    Inversion {
+        /// The probability of `x_min`.
+        f_min: f64,
+    },
+    H2pe {
+        /// The mode.
+        m: u64,
+        /// `ln(m! (n1 - m)! (k - m)! (n2 - k + m)!)`.
+        a: f64,
+        x_l: f64,
+        x_r: f64,
+        lambda_l: f64,
+        lambda_r: f64,
+        p1: f64,
+        p2: f64,
+        p3: f64,
+    },
+}
+
+/// An error returned when creating a
+/// [`Hypergeometric`](struct.Hypergeometric.html) with invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HypergeometricError {
+    /// There are more successes than values.
+    TooManySuccesses,
+    /// There are more draws than values.
+    TooManyDraws,
+}
+
+/// The distance between the mode and the smallest result below which
+/// inversion is faster than H2PE.
+const INVERSION_THRESHOLD: u64 = 10;
+
+/// The mode below which H2PE evaluates the density ratio directly rather than
+/// through logarithms of factorials.
+const EXPLICIT_THRESHOLD: u64 = 100;
+
+impl Hypergeometric {
+    /// Creates a new instance with `draws` values drawn from `total` values,
+    /// of which `successes` are successes, returning an error if `successes`
+    /// or `draws` exceeds `total`.
+    pub fn new(total: u64, successes: u64, draws: u64) -> Result<Self, HypergeometricError> {
+        if successes > total {
+            return Err(HypergeometricError::TooManySuccesses);
+        }
+        if draws > total {
+            return Err(HypergeometricError::TooManyDraws);
+        }
+
+        let failures = total - successes;
+        let swapped = successes > failures;
+        let (n1, n2) = if swapped { (failures, successes) } else { (successes, failures) };
+
+        let complemented = draws > total - draws;
+        let k = if complemented { total - draws } else { draws };
+
+        let x_min = k.saturating_sub(n2);
+        let x_max = k.min(n1);
+        let m = ((k as u128 + 1) * (n1 as u128 + 1) / (total as u128 + 2)) as u64;
+
+        let ln_f = |x: f64| -> f64 {
+            ln_factorial(x)
+                + ln_factorial(n1 as f64 - x)
+                + ln_factorial(k as f64 - x)
+                + ln_factorial((n2 - k) as f64 + x)
+        };
+
+        let repr = if m - x_min < INVERSION_THRESHOLD {
+            // ln(C(n1, x) C(n2, k - x) / C(total, k))
+            let ln_f_min = ln_factorial(n1 as f64)
+                + ln_factorial(n2 as f64)
+                + ln_factorial(k as f64)
+                + ln_factorial((total - k) as f64)
+                - ln_factorial(total as f64)
+                - ln_f(x_min as f64);
+            HypergeometricRepr::Inversion { f_min: ln_f_min.exp() }
+        } else {
+            let total = total as f64;
+            let (n1, n2, k) = (n1 as f64, n2 as f64, k as f64);
+
+            // The rectangle around the mode and the exponential tails, with
+            // the cumulative area up to the end of each
+            let a = ln_f(m as f64);
+            let var = (total - k) * k * n1 * n2 / ((total - 1.0) * total * total);
+            let d = (1.5 * var.sqrt() + 0.5).floor();
+            let x_l = m as f64 - d + 0.5;
+            let x_r = m as f64 + d + 0.5;
+            let k_l = (a - ln_f(x_l)).exp();
+            let k_r = (a - ln_f(x_r - 1.0)).exp();
+            let lambda_l = -(x_l * (n2 - k + x_l) / ((n1 - x_l + 1.0) * (k - x_l + 1.0))).ln();
+            let lambda_r = -((n1 - x_r + 1.0) * (k - x_r + 1.0) / (x_r * (n2 - k + x_r))).ln();
+            let p1 = 2.0 * d;
+            let p2 = p1 + k_l / lambda_l;
+            let p3 = p2 + k_r / lambda_r;
+            HypergeometricRepr::H2pe { m, a, x_l, x_r, lambda_l, lambda_r, p1, p2, p3 }
+        };
+
+        Ok(Hypergeometric {
+            total,
+            successes,
+            draws,
+            n1,
+            n2,
+            k,
+            swapped,
+            complemented,
+            x_min,
+            x_max,
+            repr,
+        })
+    }
+
+    /// Returns the total number of values.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of values that are successes.
+    #[inline]
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+
+    /// Returns the number of values drawn.
+    #[inline]
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Returns `f(x + 1) / f(x)` for the transformed parameters.
+    #[inline]
+    fn ratio(&self, x: u64) -> f64 {
+        let (n1, n2, k, x) = (self.n1 as f64, self.n2 as f64, self.k as f64, x as f64);
+        (n1 - x) * (k - x) / ((x + 1.0) * (n2 - k + x + 1.0))
+    }
+
+    fn try_sample_transformed<R>(&self, rng: &mut R) -> Result<u64, R::Error>
+    where
+        R: ?Sized + TryRng,
+    {
+        match self.repr {
+            HypergeometricRepr::Inversion { f_min } => 'restart: loop {
+                let mut u = f64::try_rand(rng)?;
+                let mut f = f_min;
+                let mut x = self.x_min;
+                while u > f {
+                    u -= f;
+                    if x == self.x_max {
+                        // Rounding left some probability mass unaccounted for
+                        continue 'restart;
+                    }
+                    f *= self.ratio(x);
+                    x += 1;
+                }
+                return Ok(x);
+            },
+            HypergeometricRepr::H2pe { m, a, x_l, x_r, lambda_l, lambda_r, p1, p2, p3 } => loop {
+                let u = p3 * f64::try_rand(rng)?;
+                let mut v = Open01::<f64>::try_rand(rng)?.0;
+
+                let x = if u < p1 {
+                    x_l + u
+                } else if u < p2 {
+                    let x = x_l + v.ln() / lambda_l;
+                    v *= (u - p1) * lambda_l;
+                    x
+                } else {
+                    let x = x_r - v.ln() / lambda_r;
+                    v *= (u - p2) * lambda_r;
+                    x
+                };
+                let x = x.floor();
+                if x < self.x_min as f64 || x > self.x_max as f64 {
+                    continue;
+                }
+                let x = x as u64;
+
+                let accept = if m < EXPLICIT_THRESHOLD {
+                    // Evaluate f(x) / f(m) with the recurrence between
+                    // neighbors
+                    let mut f = 1.0;
+                    if m < x {
+                        for i in m..x {
+                            f *= self.ratio(i);
+                        }
+                    } else {
+                        for i in x..m {
+                            f /= self.ratio(i);
+                        }
+                    }
+                    v <= f
+                } else {
+                    let (n1, n2, k, x) = (self.n1 as f64, self.n2 as f64, self.k as f64, x as f64);
+                    let ln_f = ln_factorial(x)
+                        + ln_factorial(n1 - x)
+                        + ln_factorial(k - x)
+                        + ln_factorial(n2 - k + x);
+                    v.ln() <= a - ln_f
+                };
+                if accept {
+                    return Ok(x);
+                }
+            },
+        }
+    }
+}
+
+impl Distribution<u64> for Hypergeometric {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<u64, R::Error> {
+        let x = self.try_sample_transformed(rng)?;
+        let x = if self.complemented { self.n1 - x } else { x };
+        Ok(if self.swapped { self.draws - x } else { x })
+    }
+}
@@ -0,0 +1,21 @@
+//! Special functions shared by the distributions.
+
+/// Returns `ln(x!)`, or `ln Γ(x + 1)` for non-integer `x >= 0`.
+///
+/// This shifts `x` up to at least 8 and then uses Stirling's series, which is
+/// accurate to within a few ULPs from there on.
+pub(crate) fn ln_factorial(x: f64) -> f64 {
+    const HALF_LN_2PI: f64 = 0.918_938_533_204_672_8;
+
+    let mut z = x + 1.0;
+    let mut shift = 1.0;
+    while z < 9.0 {
+        shift *= z;
+        z += 1.0;
+    }
+
+    let r = 1.0 / z;
+    let r2 = r * r;
+    let series = r * (1.0 / 12.0 - r2 * (1.0 / 360.0 - r2 * (1.0 / 1260.0 - r2 / 1680.0)));
+    (z - 0.5) * z.ln() - z + HALF_LN_2PI + series - shift.ln()
+}
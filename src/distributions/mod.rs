@@ -0,0 +1,173 @@
+//! Reusable objects for sampling values from probability distributions.
+//!
+//! Unlike [`Rand`](../trait.Rand.html), which ties a type to a single fixed
+//! distribution, a [`Distribution`](trait.Distribution.html) is a value that
+//! can precompute its constants once and then be sampled many times.
+
+use core::marker::PhantomData;
+use crate::prelude::*;
+
+pub mod uniform;
+pub mod weighted;
+
+#[doc(inline)]
+pub use self::uniform::Uniform;
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::weighted::{DynamicWeightedIndex, WeightedIndex};
+
+mod bernoulli;
+
+#[cfg(feature = "std")]
+mod binomial;
+
+#[cfg(feature = "std")]
+mod cauchy;
+
+#[cfg(feature = "std")]
+mod exponential;
+
+#[cfg(feature = "std")]
+mod float;
+
+#[cfg(feature = "std")]
+mod frechet;
+
+#[cfg(feature = "std")]
+mod gamma;
+
+#[cfg(feature = "std")]
+mod geometric;
+
+#[cfg(feature = "std")]
+mod gumbel;
+
+#[cfg(feature = "std")]
+mod hypergeometric;
+
+#[cfg(feature = "std")]
+mod math;
+
+#[cfg(feature = "std")]
+mod normal;
+
+#[cfg(feature = "std")]
+mod pareto;
+
+#[cfg(feature = "std")]
+mod poisson;
+
+#[cfg(feature = "std")]
+mod weibull;
+
+#[cfg(feature = "std")]
+mod ziggurat;
+
+#[cfg(feature = "std")]
+mod zipf;
+
+pub use self::bernoulli::*;
+
+#[cfg(feature = "std")]
+pub use self::{
+    binomial::*,
+    cauchy::*,
+    exponential::*,
+    float::Float,
+    frechet::*,
+    gamma::*,
+    geometric::*,
+    gumbel::*,
+    hypergeometric::*,
+    normal::*,
+    pareto::*,
+    poisson::*,
+    weibull::*,
+    zipf::*,
+};
+
+/// A type that can sample values of type `T` from a probability distribution.
+pub trait Distribution<T> {
+    /// Samples a value from `self` using `rng` without fail.
+    #[inline]
+    fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> T {
+        match self.try_sample(rng) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Samples a value from `self` using `rng`, returning an error if `rng`
+    /// fails.
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<T, R::Error>;
+
+    /// Returns an infinite iterator over values sampled from `self` using
+    /// `rng`.
+    ///
+    /// Both `self` and `rng` may be references, such as `&Uniform<T>` and
+    /// `&mut R`.
+    #[inline]
+    fn sample_iter<R: Rng>(self, rng: R) -> SampleIter<Self, R, T> where Self: Sized {
+        SampleIter { distribution: self, rng, marker: PhantomData }
+    }
+}
+
+impl<T, D: ?Sized + Distribution<T>> Distribution<T> for &D {
+    #[inline]
+    fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> T {
+        (**self).sample(rng)
+    }
+
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<T, R::Error> {
+        (**self).try_sample(rng)
+    }
+}
+
+/// An infinite iterator over values sampled from a
+/// [`Distribution`](trait.Distribution.html).
+///
+/// This is returned by
+/// [`Distribution::sample_iter`](trait.Distribution.html#method.sample_iter).
+#[derive(Clone, Copy, Debug)]
+pub struct SampleIter<D, R, T> {
+    distribution: D,
+    rng: R,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<D: Distribution<T>, R: Rng, T> Iterator for SampleIter<D, R, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        Some(self.distribution.sample(&mut self.rng))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<D: Distribution<T>, R: Rng, T> core::iter::FusedIterator for SampleIter<D, R, T> {}
+
+/// The distribution used by [`Rand`](../trait.Rand.html) for each type.
+///
+/// This allows for `Rand` types to be used wherever a `Distribution` is
+/// expected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Standard;
+
+impl<T: Rand> Distribution<T> for Standard {
+    #[inline]
+    fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> T {
+        T::rand(rng)
+    }
+
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<T, R::Error> {
+        T::try_rand(rng)
+    }
+}
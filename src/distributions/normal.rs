@@ -0,0 +1,187 @@
+//! The normal and log-normal distributions.
+
+use crate::{
+    prelude::*,
+    distributions::{Float, ziggurat::{self, NORM_F, NORM_R, NORM_X}},
+    float::Open01,
+};
+
+/// The standard normal distribution `N(0, 1)`.
+///
+/// This uses the Ziggurat method with precomputed tables, which usually costs
+/// a single random `u64` per sample. Values for `f32` are sampled as `f64` and
+/// then rounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StandardNormal;
+
+impl Distribution<f64> for StandardNormal {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<f64, R::Error> {
+        #[inline]
+        fn pdf(x: f64) -> f64 {
+            (-x * x / 2.0).exp()
+        }
+
+        // Marsaglia's method for the tail beyond `NORM_R`
+        #[inline]
+        fn tail<R: ?Sized + TryRng>(rng: &mut R, u: f64) -> Result<f64, R::Error> {
+            loop {
+                let x = Open01::<f64>::try_rand(rng)?.0.ln() / NORM_R;
+                let y = Open01::<f64>::try_rand(rng)?.0.ln();
+                if -2.0 * y >= x * x {
+                    return Ok(if u < 0.0 { x - NORM_R } else { NORM_R - x });
+                }
+            }
+        }
+
+        ziggurat::try_sample(rng, true, &NORM_X, &NORM_F, pdf, tail)
+    }
+}
+
+impl Distribution<f32> for StandardNormal {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<f32, R::Error> {
+        Distribution::<f64>::try_sample(self, rng).map(|x| x as f32)
+    }
+}
+
+/// The normal distribution `N(mean, std_dev²)`.
+///
+/// This scales [`StandardNormal`](struct.StandardNormal.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normal<F = f64> {
+    mean: F,
+    std_dev: F,
+}
+
+/// The log-normal distribution `ln N(mu, sigma²)`.
+///
+/// This is the distribution of `exp(x)` where `x` is sampled from
+/// [`Normal`](struct.Normal.html)`(mu, sigma)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogNormal<F = f64> {
+    normal: Normal<F>,
+}
+
+/// An error returned when creating a [`Normal`](struct.Normal.html) or
+/// [`LogNormal`](struct.LogNormal.html) with invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NormalError {
+    /// The mean is infinite or NaN.
+    MeanNotFinite,
+    /// The standard deviation is negative, infinite or NaN.
+    BadStdDev,
+}
+
+impl<F: Float> Normal<F> {
+    /// Creates a new instance with `mean` and `std_dev`, returning an error if
+    /// either is not finite or if `std_dev` is negative.
+    #[inline]
+    pub fn new(mean: F, std_dev: F) -> Result<Self, NormalError> {
+        if !mean.is_finite() {
+            Err(NormalError::MeanNotFinite)
+        } else if !(std_dev >= F::ZERO && std_dev.is_finite()) {
+            Err(NormalError::BadStdDev)
+        } else {
+            Ok(Normal { mean, std_dev })
+        }
+    }
+
+    /// Returns the mean of the distribution.
+    #[inline]
+    pub fn mean(&self) -> F {
+        self.mean
+    }
+
+    /// Returns the standard deviation of the distribution.
+    #[inline]
+    pub fn std_dev(&self) -> F {
+        self.std_dev
+    }
+}
+
+impl<F: Float> Distribution<F> for Normal<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let z = F::try_standard_normal(rng)?;
+        Ok(self.mean + self.std_dev * z)
+    }
+}
+
+impl<F: Float> LogNormal<F> {
+    /// Creates a new instance whose logarithm has mean `mu` and standard
+    /// deviation `sigma`, returning an error if either is not finite or if
+    /// `sigma` is negative.
+    #[inline]
+    pub fn new(mu: F, sigma: F) -> Result<Self, NormalError> {
+        Normal::new(mu, sigma).map(|normal| LogNormal { normal })
+    }
+
+    /// Returns the normal distribution of the logarithm of values.
+    #[inline]
+    pub fn normal(&self) -> &Normal<F> {
+        &self.normal
+    }
+}
+
+impl<F: Float> Distribution<F> for LogNormal<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        self.normal.try_sample(rng).map(F::exp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, assert_moments, rng};
+
+    /// Returns the standard normal cumulative distribution function, using
+    /// equation 7.1.26 from Abramowitz and Stegun for `erf`, which is accurate
+    /// to within `1.5e-7`.
+    fn standard_normal_cdf(x: f64) -> f64 {
+        let z = x.abs() / 2f64.sqrt();
+        let t = 1.0 / (1.0 + 0.327_591_1 * z);
+        let poly = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+        let erf = 1.0 - poly * (-z * z).exp();
+        0.5 * (1.0 + erf.copysign(x))
+    }
+
+    #[test]
+    fn normal_moments() {
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let mut rng = rng(1);
+        let samples: Vec<f64> = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+        assert_moments(&samples, 2.0, 9.0, 3.0);
+
+        let normal = Normal::<f32>::new(-1.0, 0.5).unwrap();
+        let samples: Vec<f64> = (0..100_000).map(|_| normal.sample(&mut rng) as f64).collect();
+        assert_moments(&samples, -1.0, 0.25, 3.0);
+    }
+
+    #[test]
+    fn normal_fits_cdf() {
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let mut rng = rng(2);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| standard_normal_cdf((x - 2.0) / 3.0));
+    }
+
+    #[test]
+    fn log_normal_fits_cdf() {
+        let log_normal = LogNormal::new(0.5, 0.8).unwrap();
+        let mut rng = rng(3);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| log_normal.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x > 0.0));
+        assert_ks(&mut samples, |x| standard_normal_cdf((x.ln() - 0.5) / 0.8));
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Normal::new(f64::NAN, 1.0), Err(NormalError::MeanNotFinite));
+        assert_eq!(Normal::new(0.0, -1.0), Err(NormalError::BadStdDev));
+        assert_eq!(Normal::new(0.0, f64::INFINITY), Err(NormalError::BadStdDev));
+        assert_eq!(LogNormal::new(0.0, f64::NAN), Err(NormalError::BadStdDev));
+        assert_eq!(Normal::new(1.0, 0.0).unwrap().sample(&mut rng(4)), 1.0);
+    }
+}
@@ -0,0 +1,83 @@
+//! The Pareto distribution.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The Pareto distribution `Pareto(scale, shape)`.
+///
+/// This inverts the cumulative distribution as `scale * u^(-1 / shape)`, so
+/// every value is at least `scale`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pareto<F = f64> {
+    scale: F,
+    shape: F,
+}
+
+/// An error returned when creating a [`Pareto`](struct.Pareto.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ParetoError {
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+    /// The shape is zero, negative, infinite or NaN.
+    BadShape,
+}
+
+impl<F: Float> Pareto<F> {
+    /// Creates a new instance with the minimum value `scale` and the tail index
+    /// `shape`, returning an error if either is not positive and finite.
+    #[inline]
+    pub fn new(scale: F, shape: F) -> Result<Self, ParetoError> {
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(ParetoError::BadScale);
+        }
+        if !(shape > F::ZERO && shape.is_finite()) {
+            return Err(ParetoError::BadShape);
+        }
+        Ok(Pareto { scale, shape })
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+
+    /// Returns the shape of the distribution.
+    #[inline]
+    pub fn shape(&self) -> F {
+        self.shape
+    }
+}
+
+impl<F: Float> Distribution<F> for Pareto<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let u = F::try_open_closed01(rng)?;
+        Ok(self.scale * u.powf(-F::ONE / self.shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, rng};
+
+    #[test]
+    fn pareto_fits_cdf() {
+        let pareto = Pareto::new(2.0, 3.0).unwrap();
+        let mut rng = rng(1);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| pareto.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| 1.0 - (2.0 / x).powf(3.0));
+
+        let pareto = Pareto::<f32>::new(1.0, 0.5).unwrap();
+        let mut samples: Vec<f64> = (0..100_000).map(|_| pareto.sample(&mut rng) as f64).collect();
+        assert_ks(&mut samples, |x| 1.0 - (1.0 / x).powf(0.5));
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Pareto::new(0.0, 1.0), Err(ParetoError::BadScale));
+        assert_eq!(Pareto::new(1.0, -1.0), Err(ParetoError::BadShape));
+        assert_eq!(Pareto::new(1.0, f64::NAN), Err(ParetoError::BadShape));
+    }
+}
@@ -0,0 +1,106 @@
+//! The Poisson distribution.
+
+use crate::{prelude::*, distributions::math::ln_factorial, float::Open01};
+
+/// The Poisson distribution `Poisson(lambda)`, which counts the events in an
+/// interval where they occur independently at an average rate of `lambda`.
+///
+/// When `lambda` is small, this multiplies uniform values until their product
+/// falls below `exp(-lambda)`. Otherwise, it uses the PTRS algorithm described
+/// in "The Transformed Rejection Method for Generating Poisson Random
+/// Variables" by Wolfgang Hörmann, whose expected cost does not depend on
+/// `lambda`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+    repr: PoissonRepr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PoissonRepr {
+    Multiplication {
+        exp_neg_lambda: f64,
+    },
+    Ptrs {
+        ln_lambda: f64,
+        a: f64,
+        b: f64,
+        inv_alpha: f64,
+        v_r: f64,
+    },
+}
+
+/// An error returned when creating a [`Poisson`](struct.Poisson.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PoissonError {
+    /// The rate is zero, negative, infinite or NaN.
+    BadLambda,
+}
+
+/// The rate below which multiplication is faster than PTRS.
+const PTRS_THRESHOLD: f64 = 10.0;
+
+impl Poisson {
+    /// Creates a new instance with the rate `lambda`, returning an error if
+    /// `lambda` is not positive and finite.
+    pub fn new(lambda: f64) -> Result<Self, PoissonError> {
+        if !(lambda > 0.0 && lambda.is_finite()) {
+            return Err(PoissonError::BadLambda);
+        }
+        let repr = if lambda < PTRS_THRESHOLD {
+            PoissonRepr::Multiplication { exp_neg_lambda: (-lambda).exp() }
+        } else {
+            let b = 0.931 + 2.53 * lambda.sqrt();
+            PoissonRepr::Ptrs {
+                ln_lambda: lambda.ln(),
+                a: -0.059 + 0.02483 * b,
+                b,
+                inv_alpha: 1.1239 + 1.1328 / (b - 3.4),
+                v_r: 0.9277 - 3.6224 / (b - 2.0),
+            }
+        };
+        Ok(Poisson { lambda, repr })
+    }
+
+    /// Returns the rate of the distribution.
+    #[inline]
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl Distribution<u64> for Poisson {
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<u64, R::Error> {
+        match self.repr {
+            PoissonRepr::Multiplication { exp_neg_lambda } => {
+                let mut k = 0;
+                let mut product = f64::try_rand(rng)?;
+                while product > exp_neg_lambda {
+                    product *= f64::try_rand(rng)?;
+                    k += 1;
+                }
+                Ok(k)
+            },
+            PoissonRepr::Ptrs { ln_lambda, a, b, inv_alpha, v_r } => loop {
+                let u = f64::try_rand(rng)? - 0.5;
+                let v = Open01::<f64>::try_rand(rng)?.0;
+                let us = 0.5 - u.abs();
+                let k = ((2.0 * a / us + b) * u + self.lambda + 0.43).floor();
+
+                // The region where the hat is below the density
+                if us >= 0.07 && v <= v_r {
+                    return Ok(k as u64);
+                }
+                if k < 0.0 || (us < 0.013 && v > us) {
+                    continue;
+                }
+                let lhs = v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln();
+                let rhs = -self.lambda + k * ln_lambda - ln_factorial(k);
+                if lhs <= rhs {
+                    return Ok(k as u64);
+                }
+            },
+        }
+    }
+}
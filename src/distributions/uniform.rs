@@ -0,0 +1,203 @@
+//! Uniform sampling within a range.
+
+use core::fmt::Debug;
+use crate::{
+    prelude::*,
+    distributions::Distribution,
+    float::{Closed01, FloatBits},
+    rand_in::WideMul,
+};
+
+/// A type that can be sampled by [`Uniform`](struct.Uniform.html).
+pub trait SampleUniform: Sized {
+    /// The precomputed state for sampling `Self` within a range.
+    type Sampler: Clone + Copy + Debug + PartialEq + Distribution<Self>;
+
+    /// Creates a sampler for `low..high`, or returns `None` if the range is
+    /// empty or invalid.
+    fn sampler(low: Self, high: Self) -> Option<Self::Sampler>;
+
+    /// Creates a sampler for `low..=high`, or returns `None` if the range is
+    /// empty or invalid.
+    fn sampler_inclusive(low: Self, high: Self) -> Option<Self::Sampler>;
+}
+
+/// A uniform distribution over a range of values.
+///
+/// This samples the same distribution as [`RandIn`](../../trait.RandIn.html)
+/// for the same range, but computes its constants up front so that repeated
+/// sampling is faster. For integers, this includes the zone of values rejected
+/// by Lemire's method.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform<T: SampleUniform>(T::Sampler);
+
+impl<T: SampleUniform> Uniform<T> {
+    /// Creates a distribution over `low..high`, or returns `None` if the range
+    /// is empty or either bound is a non-finite float.
+    #[inline]
+    pub fn new(low: T, high: T) -> Option<Self> {
+        T::sampler(low, high).map(Uniform)
+    }
+
+    /// Creates a distribution over `low..=high`, or returns `None` if the
+    /// range is empty or either bound is a non-finite float.
+    #[inline]
+    pub fn new_inclusive(low: T, high: T) -> Option<Self> {
+        T::sampler_inclusive(low, high).map(Uniform)
+    }
+}
+
+impl<T: SampleUniform> Distribution<T> for Uniform<T> {
+    #[inline]
+    fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> T {
+        self.0.sample(rng)
+    }
+
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<T, R::Error> {
+        self.0.try_sample(rng)
+    }
+}
+
+/// The sampler used by [`Uniform`](struct.Uniform.html) for integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UniformInt<T> {
+    low: T,
+    // The following are the unsigned counterparts of `T`, stored as `T`
+    range: T,
+    zone: T,
+}
+
+macro_rules! impl_int {
+    ($($int:ty => $uint:ty),+) => { $(
+        impl SampleUniform for $int {
+            type Sampler = UniformInt<$int>;
+
+            #[inline]
+            fn sampler(low: Self, high: Self) -> Option<Self::Sampler> {
+                if low < high {
+                    Self::sampler_inclusive(low, high - 1)
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn sampler_inclusive(low: Self, high: Self) -> Option<Self::Sampler> {
+                if low > high {
+                    return None;
+                }
+
+                // A range of 0 denotes the entire range of the type
+                let range = (high as $uint).wrapping_sub(low as $uint).wrapping_add(1);
+                let zone = if range == 0 {
+                    0
+                } else {
+                    // Equal to `(2^BITS - range) % range`
+                    range.wrapping_neg() % range
+                };
+
+                Some(UniformInt { low, range: range as $int, zone: zone as $int })
+            }
+        }
+
+        impl Distribution<$int> for UniformInt<$int> {
+            #[inline]
+            fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<$int, R::Error> {
+                let range = self.range as $uint;
+                let zone = self.zone as $uint;
+                let low = self.low as $uint;
+                if range == 0 {
+                    return <$int>::try_rand(rng);
+                }
+                loop {
+                    let (hi, lo) = <$uint>::try_rand(rng)?.wmul(range);
+                    if lo >= zone {
+                        return Ok(low.wrapping_add(hi) as $int);
+                    }
+                }
+            }
+        }
+    )+ }
+}
+
+impl_int! {
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+}
+
+/// The sampler used by [`Uniform`](struct.Uniform.html) for floats.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniformFloat<T> {
+    low: T,
+    scale: T,
+    // Either 1 or 2, where 2 means that `low` and `scale` were halved to keep
+    // `scale` finite
+    factor: T,
+    high: T,
+    inclusive: bool,
+}
+
+macro_rules! impl_float {
+    ($($float:ident),+) => { $(
+        impl SampleUniform for $float {
+            type Sampler = UniformFloat<$float>;
+
+            #[inline]
+            fn sampler(low: Self, high: Self) -> Option<Self::Sampler> {
+                if low < high && low.is_finite() && high.is_finite() {
+                    Some(UniformFloat::<$float>::new(low, high, false))
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn sampler_inclusive(low: Self, high: Self) -> Option<Self::Sampler> {
+                if low <= high && low.is_finite() && high.is_finite() {
+                    Some(UniformFloat::<$float>::new(low, high, true))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl UniformFloat<$float> {
+            #[inline]
+            fn new(low: $float, high: $float, inclusive: bool) -> Self {
+                let scale = high - low;
+                if scale.is_finite() {
+                    UniformFloat { low, scale, factor: 1.0, high, inclusive }
+                } else {
+                    let (low, scale) = (low * 0.5, high * 0.5 - low * 0.5);
+                    UniformFloat { low, scale, factor: 2.0, high, inclusive }
+                }
+            }
+        }
+
+        impl Distribution<$float> for UniformFloat<$float> {
+            #[inline]
+            fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<$float, R::Error> {
+                let UniformFloat { low, scale, factor, high, inclusive } = *self;
+                if inclusive {
+                    let Closed01(t) = Closed01::<$float>::try_rand(rng)?;
+
+                    // Rounding can overshoot `high`
+                    return Ok(((low + scale * t) * factor).min(high));
+                }
+                loop {
+                    let t = <$float>::closed_open(Rand::try_rand(rng)?);
+                    let value = (low + scale * t) * factor;
+
+                    // Rounding can make `value` equal to `high`, in which case
+                    // rejecting it keeps the rest of the range uniform
+                    if value < high {
+                        return Ok(value);
+                    }
+                }
+            }
+        }
+    )+ }
+}
+
+impl_float!(f32, f64);
@@ -0,0 +1,83 @@
+//! The Weibull distribution.
+
+use crate::{prelude::*, distributions::Float};
+
+/// The Weibull distribution `Weibull(scale, shape)`.
+///
+/// This inverts the cumulative distribution as `scale * (-ln u)^(1 / shape)`.
+/// A `shape` below 1 gives a heavy tail.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weibull<F = f64> {
+    scale: F,
+    shape: F,
+}
+
+/// An error returned when creating a [`Weibull`](struct.Weibull.html) with
+/// invalid parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WeibullError {
+    /// The scale is zero, negative, infinite or NaN.
+    BadScale,
+    /// The shape is zero, negative, infinite or NaN.
+    BadShape,
+}
+
+impl<F: Float> Weibull<F> {
+    /// Creates a new instance with `scale` and `shape`, returning an error if
+    /// either is not positive and finite.
+    #[inline]
+    pub fn new(scale: F, shape: F) -> Result<Self, WeibullError> {
+        if !(scale > F::ZERO && scale.is_finite()) {
+            return Err(WeibullError::BadScale);
+        }
+        if !(shape > F::ZERO && shape.is_finite()) {
+            return Err(WeibullError::BadShape);
+        }
+        Ok(Weibull { scale, shape })
+    }
+
+    /// Returns the scale of the distribution.
+    #[inline]
+    pub fn scale(&self) -> F {
+        self.scale
+    }
+
+    /// Returns the shape of the distribution.
+    #[inline]
+    pub fn shape(&self) -> F {
+        self.shape
+    }
+}
+
+impl<F: Float> Distribution<F> for Weibull<F> {
+    #[inline]
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<F, R::Error> {
+        let u = F::try_open_closed01(rng)?;
+        Ok(self.scale * (-u.ln()).powf(F::ONE / self.shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_ks, rng};
+
+    #[test]
+    fn weibull_fits_cdf() {
+        let weibull = Weibull::new(2.0, 1.5).unwrap();
+        let mut rng = rng(1);
+        let mut samples: Vec<f64> = (0..100_000).map(|_| weibull.sample(&mut rng)).collect();
+        assert_ks(&mut samples, |x| 1.0 - (-(x / 2.0).powf(1.5)).exp());
+
+        let weibull = Weibull::<f32>::new(1.0, 0.5).unwrap();
+        let mut samples: Vec<f64> = (0..100_000).map(|_| weibull.sample(&mut rng) as f64).collect();
+        assert_ks(&mut samples, |x| 1.0 - (-x.powf(0.5)).exp());
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Weibull::new(-1.0, 1.0), Err(WeibullError::BadScale));
+        assert_eq!(Weibull::new(1.0, 0.0), Err(WeibullError::BadShape));
+        assert_eq!(Weibull::new(1.0, f64::INFINITY), Err(WeibullError::BadShape));
+    }
+}
@@ -0,0 +1,693 @@
+//! Sampling indices with probabilities proportional to weights.
+
+use core::fmt::Debug;
+use crate::prelude::*;
+
+/// A type that can be used as a weight.
+///
+/// This is implemented for all primitive integers and floats. Integer weights
+/// are summed exactly, so sampling with them is exact. Float weights are summed
+/// as `f64`.
+pub trait Weight: Copy + private::Weight {}
+
+/// An error returned when weights are invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WeightedError {
+    /// There are no weights.
+    NoItem,
+    /// A weight is negative, infinite or NaN, or is zero where zero weights
+    /// are not allowed.
+    InvalidWeight,
+    /// All weights are zero.
+    AllWeightsZero,
+    /// The sum of the weights is too large to be represented.
+    Overflow,
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    /// The operations behind [`Weight`](../trait.Weight.html), kept out of
+    /// the public API.
+    pub trait Weight: Copy {
+        /// The type that weights are summed in.
+        type Sum: Copy + Debug + PartialEq + PartialOrd;
+
+        /// The type that alias table columns are filled in, which can hold a
+        /// weight multiplied by the number of weights.
+        type Scaled: Copy + PartialOrd;
+
+        /// The sum of no weights.
+        const ZERO: Self::Sum;
+
+        /// Converts `self` to a sum, or returns an error if `self` is not a
+        /// valid weight.
+        fn to_sum(self) -> Result<Self::Sum, WeightedError>;
+
+        /// Adds two sums, or returns an error on overflow.
+        fn add(a: Self::Sum, b: Self::Sum) -> Result<Self::Sum, WeightedError>;
+
+        /// Adds two sums whose total is known not to overflow.
+        fn add_in_range(a: Self::Sum, b: Self::Sum) -> Self::Sum;
+
+        /// Subtracts `b` from `a`, where `b <= a`.
+        fn sub(a: Self::Sum, b: Self::Sum) -> Self::Sum;
+
+        /// Returns whether a sum that was updated from `old` to `new` by
+        /// subtraction should be recomputed to avoid rounding errors.
+        fn needs_refresh(old: Self::Sum, new: Self::Sum) -> bool;
+
+        /// Returns `sum * n`, or an error on overflow.
+        fn scale(sum: Self::Sum, n: usize) -> Result<Self::Scaled, WeightedError>;
+
+        /// Converts `sum` to the scaled type without multiplying it.
+        fn widen(sum: Self::Sum) -> Self::Scaled;
+
+        /// Converts a scaled value that is less than a sum back to a sum.
+        fn narrow(scaled: Self::Scaled) -> Self::Sum;
+
+        /// Returns `a - b` where `b <= a`.
+        fn sub_scaled(a: Self::Scaled, b: Self::Scaled) -> Self::Scaled;
+
+        /// Returns `a + b`, which never overflows for values in the table.
+        fn add_scaled(a: Self::Scaled, b: Self::Scaled) -> Self::Scaled;
+
+        /// Returns a value uniformly in `0..total`, where `total` is non-zero.
+        fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, total: Self::Sum) -> Result<Self::Sum, R::Error>;
+    }
+}
+
+macro_rules! impl_int {
+    (@to_sum unsigned $weight:expr) => { Ok($weight as _) };
+    (@to_sum signed $weight:expr) => {
+        if $weight < 0 {
+            Err(WeightedError::InvalidWeight)
+        } else {
+            Ok($weight as _)
+        }
+    };
+    ($($sign:ident $int:ty => $sum:ty),+) => { $(
+        impl Weight for $int {}
+
+        impl private::Weight for $int {
+            type Sum = $sum;
+            type Scaled = u128;
+
+            const ZERO: $sum = 0;
+
+            #[inline]
+            fn to_sum(self) -> Result<$sum, WeightedError> {
+                impl_int!(@to_sum $sign self)
+            }
+
+            #[inline]
+            fn add(a: $sum, b: $sum) -> Result<$sum, WeightedError> {
+                a.checked_add(b).ok_or(WeightedError::Overflow)
+            }
+
+            #[inline]
+            fn add_in_range(a: $sum, b: $sum) -> $sum {
+                a + b
+            }
+
+            #[inline]
+            fn sub(a: $sum, b: $sum) -> $sum {
+                a - b
+            }
+
+            #[inline]
+            fn needs_refresh(_old: $sum, _new: $sum) -> bool {
+                false
+            }
+
+            #[inline]
+            fn scale(sum: $sum, n: usize) -> Result<u128, WeightedError> {
+                (sum as u128).checked_mul(n as u128).ok_or(WeightedError::Overflow)
+            }
+
+            #[inline]
+            fn widen(sum: $sum) -> u128 {
+                sum as u128
+            }
+
+            #[inline]
+            fn narrow(scaled: u128) -> $sum {
+                scaled as $sum
+            }
+
+            #[inline]
+            fn sub_scaled(a: u128, b: u128) -> u128 {
+                a - b
+            }
+
+            #[inline]
+            fn add_scaled(a: u128, b: u128) -> u128 {
+                a + b
+            }
+
+            #[inline]
+            fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, total: $sum) -> Result<$sum, R::Error> {
+                unsafe {
+                    // Safe because `total` is non-zero
+                    <$sum>::try_rand_in_unchecked(rng, 0..total)
+                }
+            }
+        }
+    )+ }
+}
+
+impl_int! {
+    unsigned u8 => u64,
+    unsigned u16 => u64,
+    unsigned u32 => u64,
+    unsigned u64 => u64,
+    unsigned usize => u64,
+    unsigned u128 => u128,
+    signed i8 => u64,
+    signed i16 => u64,
+    signed i32 => u64,
+    signed i64 => u64,
+    signed isize => u64,
+    signed i128 => u128
+}
+
+macro_rules! impl_float {
+    ($($float:ty),+) => { $(
+        impl Weight for $float {}
+
+        impl private::Weight for $float {
+            type Sum = f64;
+            type Scaled = f64;
+
+            const ZERO: f64 = 0.0;
+
+            #[inline]
+            fn to_sum(self) -> Result<f64, WeightedError> {
+                if self >= 0.0 && self.is_finite() {
+                    Ok(self as f64)
+                } else {
+                    Err(WeightedError::InvalidWeight)
+                }
+            }
+
+            #[inline]
+            fn add(a: f64, b: f64) -> Result<f64, WeightedError> {
+                let sum = a + b;
+                if sum.is_finite() {
+                    Ok(sum)
+                } else {
+                    Err(WeightedError::Overflow)
+                }
+            }
+
+            #[inline]
+            fn add_in_range(a: f64, b: f64) -> f64 {
+                a + b
+            }
+
+            #[inline]
+            fn sub(a: f64, b: f64) -> f64 {
+                (a - b).max(0.0)
+            }
+
+            #[inline]
+            fn needs_refresh(old: f64, new: f64) -> bool {
+                // Cancelling more than half of a sum at least doubles its
+                // relative error
+                new < old * 0.5
+            }
+
+            #[inline]
+            fn scale(sum: f64, n: usize) -> Result<f64, WeightedError> {
+                let scaled = sum * n as f64;
+                if scaled.is_finite() {
+                    Ok(scaled)
+                } else {
+                    Err(WeightedError::Overflow)
+                }
+            }
+
+            #[inline]
+            fn widen(sum: f64) -> f64 {
+                sum
+            }
+
+            #[inline]
+            fn narrow(scaled: f64) -> f64 {
+                scaled
+            }
+
+            #[inline]
+            fn sub_scaled(a: f64, b: f64) -> f64 {
+                a - b
+            }
+
+            #[inline]
+            fn add_scaled(a: f64, b: f64) -> f64 {
+                a + b
+            }
+
+            #[inline]
+            fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, total: f64) -> Result<f64, R::Error> {
+                unsafe {
+                    // Safe because `total` is positive and finite
+                    f64::try_rand_in_unchecked(rng, 0.0..total)
+                }
+            }
+        }
+    )+ }
+}
+
+impl_float!(f32, f64);
+
+/// Returns the sum of `weights`, or an error if there are no weights, they are
+/// all zero, or any weight is zero or invalid.
+#[inline]
+pub(crate) fn total<W, I>(weights: I) -> Result<W::Sum, WeightedError>
+where
+    W: Weight,
+    I: IntoIterator<Item = W>,
+{
+    let mut weights = weights.into_iter().peekable();
+    if weights.peek().is_none() {
+        return Err(WeightedError::NoItem);
+    }
+    let mut total = W::ZERO;
+    let mut any_zero = false;
+    for weight in weights {
+        let weight = weight.to_sum()?;
+        total = W::add(total, weight)?;
+        any_zero |= weight == W::ZERO;
+    }
+    if total == W::ZERO {
+        return Err(WeightedError::AllWeightsZero);
+    }
+    if any_zero {
+        return Err(WeightedError::InvalidWeight);
+    }
+    Ok(total)
+}
+
+#[cfg(feature = "std")]
+pub use self::{alias::WeightedIndex, dynamic::DynamicWeightedIndex};
+
+#[cfg(feature = "std")]
+mod alias {
+    use super::*;
+
+    /// A distribution over indices `0..n` that samples each index with
+    /// probability proportional to its weight.
+    ///
+    /// This builds an alias table with Vose's method in O(n) time, after which
+    /// each sample costs O(1): one uniform index and one comparison against
+    /// that index's threshold. Every weight must be positive; use
+    /// [`DynamicWeightedIndex`](struct.DynamicWeightedIndex.html) to keep
+    /// indices with zero weight around.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct WeightedIndex<W: Weight = f64> {
+        /// The value below which each column samples itself rather than its
+        /// alias, out of `total`.
+        thresholds: Vec<W::Sum>,
+        aliases: Vec<usize>,
+        total: W::Sum,
+    }
+
+    impl<W: Weight> WeightedIndex<W> {
+        /// Creates a new instance from `weights`, returning an error if there
+        /// are no weights, they are all zero, any weight is zero or invalid, or
+        /// their sum is too large.
+        pub fn new<I>(weights: I) -> Result<Self, WeightedError>
+        where
+            I: IntoIterator<Item = W>,
+        {
+            let weights = weights
+                .into_iter()
+                .map(|weight| weight.to_sum())
+                .collect::<Result<Vec<W::Sum>, _>>()?;
+
+            let n = weights.len();
+            if n == 0 {
+                return Err(WeightedError::NoItem);
+            }
+            let mut total = W::ZERO;
+            for &weight in &weights {
+                total = W::add(total, weight)?;
+            }
+            if total == W::ZERO {
+                return Err(WeightedError::AllWeightsZero);
+            }
+            if weights.contains(&W::ZERO) {
+                return Err(WeightedError::InvalidWeight);
+            }
+
+            // The scaled weights always sum to `total * n`, so checking it
+            // once covers every weight and every sum of two of them below
+            W::scale(total, n)?;
+
+            // Each column holds `total` and each index needs `weight * n`
+            let capacity = W::widen(total);
+            let mut scaled = Vec::with_capacity(n);
+            let mut small = Vec::new();
+            let mut large = Vec::new();
+            for (i, &weight) in weights.iter().enumerate() {
+                let weight = W::scale(weight, n)?;
+                if weight < capacity {
+                    small.push(i);
+                } else {
+                    large.push(i);
+                }
+                scaled.push(weight);
+            }
+
+            let mut thresholds = vec![total; n];
+            let mut aliases: Vec<usize> = (0..n).collect();
+            while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+                small.pop();
+                thresholds[s] = W::narrow(scaled[s]);
+                aliases[s] = l;
+
+                // Move the remainder of `s`'s column out of `l`
+                let remaining = W::add_scaled(scaled[l], scaled[s]);
+                let remaining = W::sub_scaled(remaining, capacity);
+                scaled[l] = remaining;
+                if remaining < capacity {
+                    large.pop();
+                    small.push(l);
+                }
+            }
+            // Any columns left over are full, up to rounding for floats
+
+            Ok(WeightedIndex { thresholds, aliases, total })
+        }
+
+        /// Returns the sum of all weights.
+        #[inline]
+        pub fn total_weight(&self) -> W::Sum {
+            self.total
+        }
+    }
+
+    impl<W: Weight> Distribution<usize> for WeightedIndex<W> {
+        #[inline]
+        fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<usize, R::Error> {
+            let i = unsafe {
+                // Safe because there is at least one column
+                usize::try_rand_in_unchecked(rng, 0..self.aliases.len())?
+            };
+            let x = W::try_sample_below(rng, self.total)?;
+            Ok(if x < self.thresholds[i] { i } else { self.aliases[i] })
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod dynamic {
+    use super::*;
+
+    /// A distribution over indices `0..n` whose weights can be changed between
+    /// samples.
+    ///
+    /// This stores the weights in a Fenwick tree, where each node holds the sum
+    /// of a range of weights ending at its own index. Sampling draws a value
+    /// below the total weight with [`RandIn`](../../trait.RandIn.html) and
+    /// descends the tree to find the index containing it. Sampling, updating,
+    /// pushing and removing all cost O(log n).
+    ///
+    /// Integer weights are summed exactly. For float weights, a node is
+    /// recomputed from its children whenever an update cancels more than half
+    /// of its sum, so that rounding errors don't accumulate.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct DynamicWeightedIndex<W: Weight = f64> {
+        weights: Vec<W::Sum>,
+        /// The Fenwick tree, where `tree[k - 1]` is the sum of the weights in
+        /// `(k - lowbit(k))..k`.
+        tree: Vec<W::Sum>,
+    }
+
+    /// Returns the lowest set bit of `k`.
+    #[inline]
+    fn lowbit(k: usize) -> usize {
+        k & k.wrapping_neg()
+    }
+
+    impl<W: Weight> Default for DynamicWeightedIndex<W> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<W: Weight> DynamicWeightedIndex<W> {
+        /// Creates a new instance with no weights.
+        #[inline]
+        pub fn new() -> Self {
+            DynamicWeightedIndex { weights: Vec::new(), tree: Vec::new() }
+        }
+
+        /// Creates a new instance from `weights` in O(n) time, returning an
+        /// error if any weight is invalid or their sum overflows.
+        ///
+        /// Unlike [`WeightedIndex`](struct.WeightedIndex.html), there may be
+        /// no weights or all weights may be zero.
+        pub fn from_weights<I>(weights: I) -> Result<Self, WeightedError>
+        where
+            I: IntoIterator<Item = W>,
+        {
+            let weights = weights
+                .into_iter()
+                .map(|weight| weight.to_sum())
+                .collect::<Result<Vec<W::Sum>, _>>()?;
+
+            let mut total = W::ZERO;
+            for &weight in &weights {
+                total = W::add(total, weight)?;
+            }
+
+            // Every node is at most `total`, so pushing each node into its
+            // parent never overflows
+            let mut tree = weights.clone();
+            for k in 1..=tree.len() {
+                let parent = k + lowbit(k);
+                if parent <= tree.len() {
+                    tree[parent - 1] = W::add_in_range(tree[parent - 1], tree[k - 1]);
+                }
+            }
+            Ok(DynamicWeightedIndex { weights, tree })
+        }
+
+        /// Returns the number of weights.
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.weights.len()
+        }
+
+        /// Returns whether there are no weights.
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.weights.is_empty()
+        }
+
+        /// Returns the weight at index `i`, or `None` if `i` is out of bounds.
+        #[inline]
+        pub fn weight(&self, i: usize) -> Option<W::Sum> {
+            self.weights.get(i).copied()
+        }
+
+        /// Returns the sum of all weights.
+        #[inline]
+        pub fn total_weight(&self) -> W::Sum {
+            self.prefix_sum(self.len())
+        }
+
+        /// Returns the sum of the first `k` weights.
+        #[inline]
+        fn prefix_sum(&self, mut k: usize) -> W::Sum {
+            let mut sum = W::ZERO;
+            while k > 0 {
+                sum = W::add_in_range(sum, self.tree[k - 1]);
+                k -= lowbit(k);
+            }
+            sum
+        }
+
+        /// Returns the sum of node `k` computed from its weight and children.
+        #[inline]
+        fn node_sum(&self, k: usize) -> W::Sum {
+            let mut sum = self.weights[k - 1];
+            let mut j = k - 1;
+            let start = k - lowbit(k);
+            while j > start {
+                sum = W::add_in_range(sum, self.tree[j - 1]);
+                j -= lowbit(j);
+            }
+            sum
+        }
+
+        /// Sets the weight at index `i` to `new`, where the total is known not
+        /// to overflow.
+        fn set(&mut self, i: usize, new: W::Sum) {
+            let old = self.weights[i];
+            self.weights[i] = new;
+
+            let mut k = i + 1;
+            while k <= self.len() {
+                let node = self.tree[k - 1];
+                let removed = W::sub(node, old);
+                self.tree[k - 1] = if W::needs_refresh(node, removed) {
+                    self.node_sum(k)
+                } else {
+                    W::add_in_range(removed, new)
+                };
+                k += lowbit(k);
+            }
+        }
+
+        /// Sets the weight at index `i` to `weight`, returning an error if
+        /// `weight` is invalid or the total would overflow.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `i` is out of bounds.
+        pub fn update(&mut self, i: usize, weight: W) -> Result<(), WeightedError> {
+            let new = weight.to_sum()?;
+            let old = self.weights[i];
+            W::add(W::sub(self.total_weight(), old), new)?;
+            self.set(i, new);
+            Ok(())
+        }
+
+        /// Appends `weight` at index `len()`, returning an error if `weight` is
+        /// invalid or the total would overflow.
+        pub fn push(&mut self, weight: W) -> Result<(), WeightedError> {
+            let new = weight.to_sum()?;
+            W::add(self.total_weight(), new)?;
+            self.weights.push(new);
+            self.tree.push(new);
+            let k = self.len();
+            self.tree[k - 1] = self.node_sum(k);
+            Ok(())
+        }
+
+        /// Removes and returns the weight at index `i`, replacing it with the
+        /// last weight.
+        ///
+        /// Like [`Vec::swap_remove`], this moves the index of the last weight
+        /// to `i` rather than shifting every following index.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `i` is out of bounds.
+        ///
+        /// [`Vec::swap_remove`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.swap_remove
+        pub fn remove(&mut self, i: usize) -> W::Sum {
+            let removed = self.weights[i];
+            let last = self.len() - 1;
+            if i != last {
+                // Clearing the last weight first keeps every total in range
+                let moved = self.weights[last];
+                self.set(last, W::ZERO);
+                self.set(i, moved);
+            }
+            // No other node includes the last one, so it can just be dropped
+            self.weights.pop();
+            self.tree.pop();
+            removed
+        }
+
+        /// Samples an index with probability proportional to its weight
+        /// without `rng` failing, or returns `None` if all weights are zero.
+        #[inline]
+        pub fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> Option<usize> {
+            match self.try_sample(rng) {
+                Ok(index) => index,
+                Err(err) => match err {},
+            }
+        }
+
+        /// Samples an index with probability proportional to its weight,
+        /// returning an error if `rng` fails, or `None` if all weights are
+        /// zero.
+        pub fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<Option<usize>, R::Error> {
+            let total = self.total_weight();
+            if total == W::ZERO {
+                return Ok(None);
+            }
+            // The largest power of two that is at most `len()`
+            let top = 1 << (usize::BITS - 1 - self.len().leading_zeros());
+
+            loop {
+                let mut x = W::try_sample_below(rng, total)?;
+
+                // Find the index whose range of partial sums contains `x`
+                let mut pos = 0;
+                let mut step = top;
+                while step > 0 {
+                    let next = pos + step;
+                    if next <= self.len() && self.tree[next - 1] <= x {
+                        pos = next;
+                        x = W::sub(x, self.tree[next - 1]);
+                    }
+                    step >>= 1;
+                }
+
+                // Float rounding may land past the end or on a zero weight
+                if pos < self.len() && self.weights[pos] != W::ZERO {
+                    return Ok(Some(pos));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_chi_squared, rng};
+
+    #[test]
+    fn alias_table_is_proportional() {
+        let weights = [1u32, 2, 3, 4, 10];
+        let index = WeightedIndex::new(weights.iter().copied()).unwrap();
+        let mut rng = rng(1);
+        let mut counts = [0u64; 5];
+        for _ in 0..200_000 {
+            counts[index.sample(&mut rng)] += 1;
+        }
+        let expected: Vec<f64> = weights.iter().map(|&w| w as f64).collect();
+        assert_chi_squared(&counts, &expected);
+    }
+
+    #[test]
+    fn bad_weights() {
+        assert_eq!(WeightedIndex::<u8>::new(vec![]), Err(WeightedError::NoItem));
+        assert_eq!(WeightedIndex::new(vec![0u8, 1]), Err(WeightedError::InvalidWeight));
+        assert_eq!(WeightedIndex::new(vec![1.0, 0.0]), Err(WeightedError::InvalidWeight));
+        assert_eq!(WeightedIndex::new(vec![-1i32, 2]), Err(WeightedError::InvalidWeight));
+        assert_eq!(WeightedIndex::new(vec![1.0, f64::NAN]), Err(WeightedError::InvalidWeight));
+        assert_eq!(WeightedIndex::new(vec![0u8, 0]), Err(WeightedError::AllWeightsZero));
+
+        let mut rng = rng(3);
+        assert_eq!([1, 2].get_rand_weighted(&mut rng, |_| 0u8), Err(WeightedError::AllWeightsZero));
+        assert_eq!([1, 2].get_rand_weighted(&mut rng, |&x| x - 1), Err(WeightedError::InvalidWeight));
+        assert_eq!([1, 2].get_rand_weighted(&mut rng, |&x| -x), Err(WeightedError::InvalidWeight));
+        assert_eq!([0u8; 0].get_rand_weighted(&mut rng, |&x| x), Err(WeightedError::NoItem));
+        assert!([1, 2].get_rand_weighted(&mut rng, |&x| x).is_ok());
+    }
+
+    #[test]
+    fn huge_totals_overflow() {
+        assert_eq!(WeightedIndex::new(vec![u128::MAX / 2, 1]), Err(WeightedError::Overflow));
+        assert_eq!(WeightedIndex::new(vec![i128::MAX, i128::MAX]), Err(WeightedError::Overflow));
+        assert_eq!(WeightedIndex::new(vec![f64::MAX, 1.0]), Err(WeightedError::Overflow));
+
+        // Just below the limit, the table is still exact
+        let max = u128::MAX / 2;
+        let index = WeightedIndex::new(vec![max - max / 4, max / 4]).unwrap();
+        let mut rng = rng(2);
+        let mut counts = [0u64; 2];
+        for _ in 0..100_000 {
+            counts[index.sample(&mut rng)] += 1;
+        }
+        assert_chi_squared(&counts, &[3.0, 1.0]);
+    }
+}
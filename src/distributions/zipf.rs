@@ -0,0 +1,166 @@
+//! The Zipf distribution.
+
+use crate::prelude::*;
+
+/// The Zipf distribution `Zipf(n, s)`, which samples each integer `k` in
+/// `1..=n` with probability proportional to `k^-s`.
+///
+/// This uses the method described in "Rejection-Inversion to Generate
+/// Variates from Monotone Discrete Distributions" by Wolfgang Hörmann and
+/// Gerhard Derflinger, whose expected cost does not depend on `n`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Zipf {
+    n: u64,
+    s: f64,
+    /// `H(1.5) - 1`, where `H` integrates the hat function `h(x) = x^-s`.
+    h_integral_1: f64,
+    /// `H(n + 0.5)`.
+    h_integral_n: f64,
+    /// The width around each integer where the hat is known to be accepted.
+    squeeze: f64,
+}
+
+/// An error returned when creating a [`Zipf`](struct.Zipf.html) with invalid
+/// parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ZipfError {
+    /// The number of elements is 0.
+    EmptyRange,
+    /// The exponent is negative, infinite or NaN.
+    BadExponent,
+}
+
+impl Zipf {
+    /// Creates a new instance over `1..=n` with the exponent `s`, returning an
+    /// error if `n` is 0 or if `s` is negative or not finite.
+    pub fn new(n: u64, s: f64) -> Result<Self, ZipfError> {
+        if n == 0 {
+            return Err(ZipfError::EmptyRange);
+        }
+        if !(s >= 0.0 && s.is_finite()) {
+            return Err(ZipfError::BadExponent);
+        }
+        let h_integral_1 = h_integral(1.5, s) - 1.0;
+        let h_integral_n = h_integral(n as f64 + 0.5, s);
+        let squeeze = 2.0 - h_integral_inverse(h_integral(2.5, s) - h(2.0, s), s);
+        Ok(Zipf { n, s, h_integral_1, h_integral_n, squeeze })
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the exponent.
+    #[inline]
+    pub fn s(&self) -> f64 {
+        self.s
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn try_sample<R: ?Sized + TryRng>(&self, rng: &mut R) -> Result<u64, R::Error> {
+        loop {
+            let u = self.h_integral_n
+                + f64::try_rand(rng)? * (self.h_integral_1 - self.h_integral_n);
+            let x = h_integral_inverse(u, self.s);
+
+            // Round to the nearest integer within bounds, which rounding errors
+            // may otherwise push it out of
+            let k = ((x + 0.5) as u64).max(1).min(self.n);
+            let k_f = k as f64;
+            if k_f - x <= self.squeeze || u >= h_integral(k_f + 0.5, self.s) - h(k_f, self.s) {
+                return Ok(k);
+            }
+        }
+    }
+}
+
+/// The hat function `x^-s`.
+#[inline]
+fn h(x: f64, s: f64) -> f64 {
+    (-s * x.ln()).exp()
+}
+
+/// The integral of `h`, which is `(x^(1 - s) - 1) / (1 - s)`, or `ln(x)` if `s`
+/// is 1.
+#[inline]
+fn h_integral(x: f64, s: f64) -> f64 {
+    let ln_x = x.ln();
+    exp_m1_div((1.0 - s) * ln_x) * ln_x
+}
+
+/// The inverse of `h_integral`.
+#[inline]
+fn h_integral_inverse(x: f64, s: f64) -> f64 {
+    // Rounding errors may push `t` below the domain of `ln_1p`
+    let t = (x * (1.0 - s)).max(-1.0);
+    (ln_1p_div(t) * x).exp()
+}
+
+/// Returns `ln(1 + x) / x`, continued to 1 at `x = 0`.
+#[inline]
+fn ln_1p_div(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+/// Returns `(exp(x) - 1) / x`, continued to 1 at `x = 0`.
+#[inline]
+fn exp_m1_div(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{assert_chi_squared, rng};
+
+    fn assert_fits(n: u64, s: f64, seed: u64) {
+        let zipf = Zipf::new(n, s).unwrap();
+        let mut rng = rng(seed);
+        let mut counts = vec![0u64; n as usize];
+        for _ in 0..200_000 {
+            let k = zipf.sample(&mut rng);
+            assert!((1..=n).contains(&k));
+            counts[k as usize - 1] += 1;
+        }
+
+        // `assert_chi_squared` divides by the sum, which is `H(n, s)`
+        let expected: Vec<f64> = (1..=n).map(|k| (k as f64).powf(-s)).collect();
+        assert_chi_squared(&counts, &expected);
+    }
+
+    #[test]
+    fn zipf_fits_pmf() {
+        assert_fits(10, 1.0, 1);
+        assert_fits(10, 2.5, 2);
+        assert_fits(20, 0.5, 3);
+        assert_fits(7, 0.0, 4);
+        assert_fits(2, 1.0, 5);
+        assert_fits(1000, 1.1, 7);
+    }
+
+    #[test]
+    fn single_element() {
+        let zipf = Zipf::new(1, 1.5).unwrap();
+        let mut rng = rng(6);
+        assert!((0..100).all(|_| zipf.sample(&mut rng) == 1));
+    }
+
+    #[test]
+    fn bad_parameters() {
+        assert_eq!(Zipf::new(0, 1.0), Err(ZipfError::EmptyRange));
+        assert_eq!(Zipf::new(10, -1.0), Err(ZipfError::BadExponent));
+        assert_eq!(Zipf::new(10, f64::NAN), Err(ZipfError::BadExponent));
+        assert_eq!(Zipf::new(10, f64::INFINITY), Err(ZipfError::BadExponent));
+    }
+}
@@ -0,0 +1,117 @@
+//! Sampling distinct indices without replacement.
+
+use std::collections::HashSet;
+use crate::prelude::*;
+
+/// The largest amount for which Floyd's algorithm is used, since it searches
+/// the indices chosen so far for each new one.
+const FLOYD_MAX_AMOUNT: usize = 32;
+
+/// Returns `amount` distinct indices uniformly from `0..length` without `rng`
+/// failing, or `None` if `amount` is greater than `length`.
+///
+/// The indices are in random order, so every ordered selection is equally
+/// likely and any prefix of the result is itself a uniform sample.
+///
+/// The algorithm is chosen based on `amount` and `length`:
+///
+/// - Floyd's algorithm for small amounts, which takes O(amount²) time and
+///   O(amount) memory.
+/// - A partial Fisher-Yates shuffle of `0..length` when more than a quarter of
+///   the indices are chosen, which takes O(length) time and memory.
+/// - Rejection sampling into a hash set otherwise, which takes O(amount)
+///   expected time and memory.
+#[inline]
+pub fn sample<R: ?Sized + Rng>(rng: &mut R, length: usize, amount: usize) -> Option<Vec<usize>> {
+    match try_sample(rng, length, amount) {
+        Ok(indices) => indices,
+        Err(err) => match err {},
+    }
+}
+
+/// Returns `amount` distinct indices uniformly from `0..length`, returning an
+/// error if `rng` fails, or `None` if `amount` is greater than `length`.
+///
+/// See [`sample`](fn.sample.html) for details.
+#[inline]
+pub fn try_sample<R: ?Sized + TryRng>(rng: &mut R, length: usize, amount: usize) -> Result<Option<Vec<usize>>, R::Error> {
+    if amount > length {
+        Ok(None)
+    } else {
+        unsafe { try_sample_unchecked(rng, length, amount).map(Some) }
+    }
+}
+
+/// Returns `amount` distinct indices uniformly from `0..length` without `rng`
+/// failing and without checking whether `amount` is greater than `length`.
+///
+/// # Safety
+///
+/// `amount` must not be greater than `length`.
+#[inline]
+pub unsafe fn sample_unchecked<R: ?Sized + Rng>(rng: &mut R, length: usize, amount: usize) -> Vec<usize> {
+    match try_sample_unchecked(rng, length, amount) {
+        Ok(indices) => indices,
+        Err(err) => match err {},
+    }
+}
+
+/// Returns `amount` distinct indices uniformly from `0..length` without
+/// checking whether `amount` is greater than `length`, returning an error if
+/// `rng` fails.
+///
+/// # Safety
+///
+/// `amount` must not be greater than `length`.
+pub unsafe fn try_sample_unchecked<R: ?Sized + TryRng>(rng: &mut R, length: usize, amount: usize) -> Result<Vec<usize>, R::Error> {
+    if amount <= FLOYD_MAX_AMOUNT {
+        try_sample_floyd(rng, length, amount)
+    } else if amount > length / 4 {
+        try_sample_fisher_yates(rng, length, amount)
+    } else {
+        try_sample_rejection(rng, length, amount)
+    }
+}
+
+/// Floyd's algorithm, modified so that the result is in random order.
+///
+/// Each step picks `t` from `0..=j`. If `t` was already chosen, `j` replaces it
+/// and `t` moves to the end, which keeps every ordering equally likely.
+unsafe fn try_sample_floyd<R: ?Sized + TryRng>(rng: &mut R, length: usize, amount: usize) -> Result<Vec<usize>, R::Error> {
+    let mut indices = Vec::with_capacity(amount);
+    for j in (length - amount)..length {
+        // Safe because `0..=j` is never empty
+        let t = usize::try_rand_in_unchecked(rng, 0..=j)?;
+        if let Some(pos) = indices.iter().position(|&i| i == t) {
+            indices[pos] = j;
+        }
+        indices.push(t);
+    }
+    Ok(indices)
+}
+
+/// The first `amount` steps of a Fisher-Yates shuffle of `0..length`.
+unsafe fn try_sample_fisher_yates<R: ?Sized + TryRng>(rng: &mut R, length: usize, amount: usize) -> Result<Vec<usize>, R::Error> {
+    let mut indices: Vec<usize> = (0..length).collect();
+    for i in 0..amount {
+        // Safe because `i < amount <= length`
+        let j = usize::try_rand_in_unchecked(rng, i..length)?;
+        indices.swap(i, j);
+    }
+    indices.truncate(amount);
+    Ok(indices)
+}
+
+/// Draws from `0..length` until `amount` distinct indices have been seen.
+unsafe fn try_sample_rejection<R: ?Sized + TryRng>(rng: &mut R, length: usize, amount: usize) -> Result<Vec<usize>, R::Error> {
+    let mut seen = HashSet::with_capacity(amount);
+    let mut indices = Vec::with_capacity(amount);
+    while indices.len() < amount {
+        // Safe because `length >= amount * 4 > 0`
+        let i = usize::try_rand_in_unchecked(rng, 0..length)?;
+        if seen.insert(i) {
+            indices.push(i);
+        }
+    }
+    Ok(indices)
+}
@@ -1,4 +1,7 @@
 //! Extension traits.
 
+#[cfg(feature = "std")]
+pub mod index;
+
 mod slice;
 pub use self::slice::*;
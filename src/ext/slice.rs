@@ -1,4 +1,13 @@
-use crate::prelude::*;
+use crate::{
+    prelude::*,
+    distributions::weighted::{self, Weight, WeightedError},
+};
+
+#[cfg(feature = "std")]
+use core::{iter::FusedIterator, marker::PhantomData};
+
+#[cfg(feature = "std")]
+use crate::ext::index;
 
 /// Extended functionality for slices.
 pub trait SliceExt<A> {
@@ -18,19 +27,130 @@ pub trait SliceExt<A> {
 
     /// Returns a random shared reference in `self` without `rng` failing and
     /// without checking whether `self` is empty.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn get_rand_unchecked<'a, R: ?Sized + Rng>(&'a self, rng: &mut R) -> &'a A;
 
     /// Returns a random shared reference in `self` without checking whether
     /// `self` is empty, returning an error if `rng` fails.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn try_get_rand_unchecked<'a, R: ?Sized + TryRng>(&'a self, rng: &mut R) -> Result<&'a A, R::Error>;
 
     /// Returns a random mutable reference in `self` without `rng` failing and
     /// without checking whether `self` is empty.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn get_rand_mut_unchecked<'a, R: ?Sized + Rng>(&'a mut self, rng: &mut R) -> &'a mut A;
 
     /// Returns a random mutable reference in `self` without checking whether
     /// `self` is empty, returning an error if `rng` fails.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn try_get_rand_mut_unchecked<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R) -> Result<&'a mut A, R::Error>;
+
+    /// Returns a random shared reference in `self` with probability
+    /// proportional to `weight`, without `rng` failing.
+    ///
+    /// Returns an error if `self` is empty, all weights are zero, or any weight
+    /// is zero or invalid.
+    ///
+    /// This scans `self` twice and calls `weight` on each element both times,
+    /// so it should return the same weight for the same element. To sample
+    /// many times from the same weights, use
+    /// [`WeightedIndex`](../distributions/struct.WeightedIndex.html) instead.
+    fn get_rand_weighted<'a, R, W, F>(&'a self, rng: &mut R, weight: F) -> Result<&'a A, WeightedError>
+        where R: ?Sized + Rng, W: Weight, F: FnMut(&A) -> W;
+
+    /// Returns a random shared reference in `self` with probability
+    /// proportional to `weight`, returning an error if `rng` fails.
+    fn try_get_rand_weighted<'a, R, W, F>(&'a self, rng: &mut R, weight: F) -> Result<Result<&'a A, WeightedError>, R::Error>
+        where R: ?Sized + TryRng, W: Weight, F: FnMut(&A) -> W;
+
+    /// Returns a random mutable reference in `self` with probability
+    /// proportional to `weight`, without `rng` failing.
+    fn get_rand_weighted_mut<'a, R, W, F>(&'a mut self, rng: &mut R, weight: F) -> Result<&'a mut A, WeightedError>
+        where R: ?Sized + Rng, W: Weight, F: FnMut(&A) -> W;
+
+    /// Returns a random mutable reference in `self` with probability
+    /// proportional to `weight`, returning an error if `rng` fails.
+    fn try_get_rand_weighted_mut<'a, R, W, F>(&'a mut self, rng: &mut R, weight: F) -> Result<Result<&'a mut A, WeightedError>, R::Error>
+        where R: ?Sized + TryRng, W: Weight, F: FnMut(&A) -> W;
+
+    /// Returns an iterator over `amount` distinct random shared references in
+    /// `self` without `rng` failing, or `None` if `amount` is greater than
+    /// `self.len()`.
+    ///
+    /// The elements are chosen with [`index::sample`](index/fn.sample.html),
+    /// so every ordered selection is equally likely.
+    #[cfg(feature = "std")]
+    fn choose_multiple<'a, R: ?Sized + Rng>(&'a self, rng: &mut R, amount: usize) -> Option<ChooseMultiple<'a, A>>;
+
+    /// Returns an iterator over `amount` distinct random shared references in
+    /// `self`, returning an error if `rng` fails, or `None` if `amount` is
+    /// greater than `self.len()`.
+    #[cfg(feature = "std")]
+    fn try_choose_multiple<'a, R: ?Sized + TryRng>(&'a self, rng: &mut R, amount: usize) -> Result<Option<ChooseMultiple<'a, A>>, R::Error>;
+
+    /// Returns an iterator over `amount` distinct random mutable references in
+    /// `self` without `rng` failing, or `None` if `amount` is greater than
+    /// `self.len()`.
+    #[cfg(feature = "std")]
+    fn choose_multiple_mut<'a, R: ?Sized + Rng>(&'a mut self, rng: &mut R, amount: usize) -> Option<ChooseMultipleMut<'a, A>>;
+
+    /// Returns an iterator over `amount` distinct random mutable references in
+    /// `self`, returning an error if `rng` fails, or `None` if `amount` is
+    /// greater than `self.len()`.
+    #[cfg(feature = "std")]
+    fn try_choose_multiple_mut<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R, amount: usize) -> Result<Option<ChooseMultipleMut<'a, A>>, R::Error>;
+
+    /// Returns an iterator over `amount` distinct random shared references in
+    /// `self` without `rng` failing and without checking whether `amount` is
+    /// greater than `self.len()`.
+    ///
+    /// # Safety
+    ///
+    /// `amount` must not be greater than `self.len()`.
+    #[cfg(feature = "std")]
+    unsafe fn choose_multiple_unchecked<'a, R: ?Sized + Rng>(&'a self, rng: &mut R, amount: usize) -> ChooseMultiple<'a, A>;
+
+    /// Returns an iterator over `amount` distinct random shared references in
+    /// `self` without checking whether `amount` is greater than `self.len()`,
+    /// returning an error if `rng` fails.
+    ///
+    /// # Safety
+    ///
+    /// `amount` must not be greater than `self.len()`.
+    #[cfg(feature = "std")]
+    unsafe fn try_choose_multiple_unchecked<'a, R: ?Sized + TryRng>(&'a self, rng: &mut R, amount: usize) -> Result<ChooseMultiple<'a, A>, R::Error>;
+
+    /// Returns an iterator over `amount` distinct random mutable references in
+    /// `self` without `rng` failing and without checking whether `amount` is
+    /// greater than `self.len()`.
+    ///
+    /// # Safety
+    ///
+    /// `amount` must not be greater than `self.len()`.
+    #[cfg(feature = "std")]
+    unsafe fn choose_multiple_mut_unchecked<'a, R: ?Sized + Rng>(&'a mut self, rng: &mut R, amount: usize) -> ChooseMultipleMut<'a, A>;
+
+    /// Returns an iterator over `amount` distinct random mutable references in
+    /// `self` without checking whether `amount` is greater than `self.len()`,
+    /// returning an error if `rng` fails.
+    ///
+    /// # Safety
+    ///
+    /// `amount` must not be greater than `self.len()`.
+    #[cfg(feature = "std")]
+    unsafe fn try_choose_multiple_mut_unchecked<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R, amount: usize) -> Result<ChooseMultipleMut<'a, A>, R::Error>;
 }
 
 impl<A> SliceExt<A> for [A] {
@@ -73,4 +193,214 @@ impl<A> SliceExt<A> for [A] {
     unsafe fn try_get_rand_mut_unchecked<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R) -> Result<&'a mut A, R::Error> {
         <&mut A>::try_rand_in_unchecked(rng, self)
     }
+
+    #[inline]
+    fn get_rand_weighted<'a, R, W, F>(&'a self, rng: &mut R, weight: F) -> Result<&'a A, WeightedError>
+        where R: ?Sized + Rng, W: Weight, F: FnMut(&A) -> W
+    {
+        match self.try_get_rand_weighted(rng, weight) {
+            Ok(result) => result,
+            Err(err) => match err {},
+        }
+    }
+
+    #[inline]
+    fn try_get_rand_weighted<'a, R, W, F>(&'a self, rng: &mut R, weight: F) -> Result<Result<&'a A, WeightedError>, R::Error>
+        where R: ?Sized + TryRng, W: Weight, F: FnMut(&A) -> W
+    {
+        Ok(try_weighted_index(self, rng, weight)?.map(|i| &self[i]))
+    }
+
+    #[inline]
+    fn get_rand_weighted_mut<'a, R, W, F>(&'a mut self, rng: &mut R, weight: F) -> Result<&'a mut A, WeightedError>
+        where R: ?Sized + Rng, W: Weight, F: FnMut(&A) -> W
+    {
+        match self.try_get_rand_weighted_mut(rng, weight) {
+            Ok(result) => result,
+            Err(err) => match err {},
+        }
+    }
+
+    #[inline]
+    fn try_get_rand_weighted_mut<'a, R, W, F>(&'a mut self, rng: &mut R, weight: F) -> Result<Result<&'a mut A, WeightedError>, R::Error>
+        where R: ?Sized + TryRng, W: Weight, F: FnMut(&A) -> W
+    {
+        Ok(try_weighted_index(self, rng, weight)?.map(move |i| &mut self[i]))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn choose_multiple<'a, R: ?Sized + Rng>(&'a self, rng: &mut R, amount: usize) -> Option<ChooseMultiple<'a, A>> {
+        match self.try_choose_multiple(rng, amount) {
+            Ok(iter) => iter,
+            Err(err) => match err {},
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn try_choose_multiple<'a, R: ?Sized + TryRng>(&'a self, rng: &mut R, amount: usize) -> Result<Option<ChooseMultiple<'a, A>>, R::Error> {
+        if amount > self.len() {
+            Ok(None)
+        } else {
+            unsafe { self.try_choose_multiple_unchecked(rng, amount).map(Some) }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn choose_multiple_mut<'a, R: ?Sized + Rng>(&'a mut self, rng: &mut R, amount: usize) -> Option<ChooseMultipleMut<'a, A>> {
+        match self.try_choose_multiple_mut(rng, amount) {
+            Ok(iter) => iter,
+            Err(err) => match err {},
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn try_choose_multiple_mut<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R, amount: usize) -> Result<Option<ChooseMultipleMut<'a, A>>, R::Error> {
+        if amount > self.len() {
+            Ok(None)
+        } else {
+            unsafe { self.try_choose_multiple_mut_unchecked(rng, amount).map(Some) }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    unsafe fn choose_multiple_unchecked<'a, R: ?Sized + Rng>(&'a self, rng: &mut R, amount: usize) -> ChooseMultiple<'a, A> {
+        match self.try_choose_multiple_unchecked(rng, amount) {
+            Ok(iter) => iter,
+            Err(err) => match err {},
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    unsafe fn try_choose_multiple_unchecked<'a, R: ?Sized + TryRng>(&'a self, rng: &mut R, amount: usize) -> Result<ChooseMultiple<'a, A>, R::Error> {
+        let indices = index::try_sample_unchecked(rng, self.len(), amount)?;
+        Ok(ChooseMultiple { slice: self, indices: indices.into_iter() })
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    unsafe fn choose_multiple_mut_unchecked<'a, R: ?Sized + Rng>(&'a mut self, rng: &mut R, amount: usize) -> ChooseMultipleMut<'a, A> {
+        match self.try_choose_multiple_mut_unchecked(rng, amount) {
+            Ok(iter) => iter,
+            Err(err) => match err {},
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    unsafe fn try_choose_multiple_mut_unchecked<'a, R: ?Sized + TryRng>(&'a mut self, rng: &mut R, amount: usize) -> Result<ChooseMultipleMut<'a, A>, R::Error> {
+        let indices = index::try_sample_unchecked(rng, self.len(), amount)?;
+        Ok(ChooseMultipleMut {
+            ptr: self.as_mut_ptr(),
+            indices: indices.into_iter(),
+            marker: PhantomData,
+        })
+    }
+}
+
+/// Returns a random index in `slice` with probability proportional to
+/// `weight`, by summing the weights and then scanning for the sampled point.
+fn try_weighted_index<A, R, W, F>(slice: &[A], rng: &mut R, mut weight: F) -> Result<Result<usize, WeightedError>, R::Error>
+    where R: ?Sized + TryRng, W: Weight, F: FnMut(&A) -> W
+{
+    let total = match weighted::total(slice.iter().map(&mut weight)) {
+        Ok(total) => total,
+        Err(err) => return Ok(Err(err)),
+    };
+    let x = W::try_sample_below(rng, total)?;
+
+    // The partial sums are computed in the same order as `total`, so the last
+    // weight always reaches it, even with float rounding
+    let mut sum = W::ZERO;
+    for (i, item) in slice.iter().enumerate() {
+        sum = match weight(item).to_sum().and_then(|w| W::add(sum, w)) {
+            Ok(sum) => sum,
+            Err(err) => return Ok(Err(err)),
+        };
+        if x < sum {
+            return Ok(Ok(i));
+        }
+    }
+    Ok(Err(WeightedError::AllWeightsZero))
+}
+
+/// An iterator over distinct random shared references in a slice.
+///
+/// This is returned by
+/// [`SliceExt::choose_multiple`](trait.SliceExt.html#tymethod.choose_multiple).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct ChooseMultiple<'a, A> {
+    slice: &'a [A],
+    indices: std::vec::IntoIter<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, A> Iterator for ChooseMultiple<'a, A> {
+    type Item = &'a A;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a A> {
+        self.indices.next().map(|i| &self.slice[i])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> ExactSizeIterator for ChooseMultiple<'_, A> {}
+
+#[cfg(feature = "std")]
+impl<A> FusedIterator for ChooseMultiple<'_, A> {}
+
+/// An iterator over distinct random mutable references in a slice.
+///
+/// This is returned by
+/// [`SliceExt::choose_multiple_mut`](trait.SliceExt.html#tymethod.choose_multiple_mut).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ChooseMultipleMut<'a, A> {
+    ptr: *mut A,
+    indices: std::vec::IntoIter<usize>,
+    marker: PhantomData<&'a mut [A]>,
+}
+
+// Safe because this behaves like `&mut [A]`
+#[cfg(feature = "std")]
+unsafe impl<A: Send> Send for ChooseMultipleMut<'_, A> {}
+
+#[cfg(feature = "std")]
+unsafe impl<A: Sync> Sync for ChooseMultipleMut<'_, A> {}
+
+#[cfg(feature = "std")]
+impl<'a, A> Iterator for ChooseMultipleMut<'a, A> {
+    type Item = &'a mut A;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut A> {
+        self.indices.next().map(|i| unsafe {
+            // Safe because the indices are distinct and within the slice, so
+            // no two references alias
+            &mut *self.ptr.add(i)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
 }
+
+#[cfg(feature = "std")]
+impl<A> ExactSizeIterator for ChooseMultipleMut<'_, A> {}
+
+#[cfg(feature = "std")]
+impl<A> FusedIterator for ChooseMultipleMut<'_, A> {}
@@ -0,0 +1,164 @@
+//! Floating point generation.
+//!
+//! [`Rand`](../trait.Rand.html) for `f32` and `f64` generates values uniformly
+//! in `[0, 1)` from the upper 24 or 53 bits of a random integer, so that each
+//! value is a multiple of 2<sup>-24</sup> or 2<sup>-53</sup> respectively. The
+//! wrapper types in this module select other intervals or a higher precision.
+
+use crate::prelude::*;
+
+/// A value generated uniformly in the open interval `(0, 1)`.
+///
+/// Each value is an odd multiple of 2<sup>-24</sup> for `f32` or
+/// 2<sup>-53</sup> for `f64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Open01<F>(pub F);
+
+/// A value generated uniformly in the half-open interval `(0, 1]`.
+///
+/// Each value is a non-zero multiple of 2<sup>-24</sup> for `f32` or
+/// 2<sup>-53</sup> for `f64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct OpenClosed01<F>(pub F);
+
+/// A value generated uniformly in the closed interval `[0, 1]`.
+///
+/// Each value is a multiple of 1 / (2<sup>24</sup> - 1) for `f32` or
+/// 1 / (2<sup>53</sup> - 1) for `f64`, rounded to the nearest float.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Closed01<F>(pub F);
+
+/// A value generated uniformly in the half-open interval `[0, 1)` that can be
+/// any representable float in that interval, including subnormals.
+///
+/// Each float `x` is generated with probability equal to the distance between
+/// `x` and the next representable float, as if a real number was chosen
+/// uniformly and then rounded down. The exponent is chosen from a geometric
+/// distribution and the fraction bits are chosen uniformly, as in
+/// [Downey's algorithm](http://allendowney.com/research/rand/). This usually
+/// consumes a single random integer, but may consume more for values close to
+/// zero.
+///
+/// This is slower than the other methods but is correct near zero, where the
+/// fixed-precision methods can only generate 0 or multiples of their spacing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct FullPrecision<F>(pub F);
+
+/// Conversions from random bits to floats.
+pub(crate) trait FloatBits: Sized {
+    /// The unsigned integer with the same width as `Self`.
+    type Bits;
+
+    /// Converts the upper bits of `bits` to a value in `[0, 1)`.
+    fn closed_open(bits: Self::Bits) -> Self;
+}
+
+macro_rules! imp {
+    ($($float:ident, $uint:ty, $next:ident, $try_next:ident, $fraction_bits:expr, $max_zeros:expr;)+) => { $(
+        impl FloatBits for $float {
+            type Bits = $uint;
+
+            #[inline]
+            fn closed_open(bits: $uint) -> Self {
+                const PRECISION: u32 = $fraction_bits + 1;
+                const SCALE: $float = 1.0 / (1u64 << PRECISION) as $float;
+                (bits >> (<$uint>::BITS - PRECISION)) as $float * SCALE
+            }
+        }
+
+        impl Rand for Open01<$float> {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                match Self::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                const SCALE: $float = 1.0 / (1u64 << $fraction_bits) as $float;
+                let bits = rng.$try_next()? >> (<$uint>::BITS - $fraction_bits);
+                Ok(Open01((bits as $float + 0.5) * SCALE))
+            }
+        }
+
+        impl Rand for OpenClosed01<$float> {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                match Self::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                const PRECISION: u32 = $fraction_bits + 1;
+                const SCALE: $float = 1.0 / (1u64 << PRECISION) as $float;
+                let bits = rng.$try_next()? >> (<$uint>::BITS - PRECISION);
+                Ok(OpenClosed01((bits + 1) as $float * SCALE))
+            }
+        }
+
+        impl Rand for Closed01<$float> {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                match Self::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                const PRECISION: u32 = $fraction_bits + 1;
+                const SCALE: $float = 1.0 / ((1u64 << PRECISION) - 1) as $float;
+                let bits = rng.$try_next()? >> (<$uint>::BITS - PRECISION);
+                Ok(Closed01(bits as $float * SCALE))
+            }
+        }
+
+        impl Rand for FullPrecision<$float> {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                match Self::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                const FRACTION_MASK: $uint = (1 << $fraction_bits) - 1;
+                const EXPONENT_BITS: u32 = <$uint>::BITS - $fraction_bits;
+
+                // The fraction comes from the lower bits, and the remaining
+                // upper bits begin the geometric draw of the exponent
+                let bits = rng.$try_next()?;
+                let fraction = bits & FRACTION_MASK;
+                let mut zeros = (bits >> $fraction_bits).leading_zeros() - $fraction_bits;
+
+                if zeros == EXPONENT_BITS {
+                    loop {
+                        let bits = rng.$try_next()?;
+                        zeros += bits.leading_zeros();
+                        if bits != 0 || zeros >= $max_zeros {
+                            break;
+                        }
+                    }
+                }
+
+                // Each leading zero halves the binade, bottoming out at the
+                // subnormals whose exponent field is 0
+                let exponent = ($max_zeros - zeros.min($max_zeros)) as $uint;
+                let value = $float::from_bits((exponent << $fraction_bits) | fraction);
+                Ok(FullPrecision(value))
+            }
+        }
+    )+ }
+}
+
+imp! {
+    f32, u32, next_u32, try_next_u32, 23, 126;
+    f64, u64, next_u64, try_next_u64, 52, 1022;
+}
@@ -10,8 +10,14 @@ extern crate core;
 mod rand;
 mod rand_in;
 mod shuffle;
+#[cfg(test)]
+mod test_util;
+#[cfg(all(feature = "std", unix))]
+mod random;
 pub mod arch;
+pub mod distributions;
 pub mod ext;
+pub mod float;
 pub mod platform;
 pub mod rng;
 
@@ -21,8 +27,9 @@ pub mod prelude {
     pub use crate::{
         rand::Rand,
         rand_in::RandIn,
+        distributions::Distribution,
         ext::SliceExt,
-        rng::{Rng, TryRng, CryptoRng},
+        rng::{Rng, TryRng, CryptoRng, SeedableRng},
         shuffle::Shuffle,
     };
 }
@@ -34,3 +41,13 @@ pub use self::{
     rng::{Rng, TryRng},
     shuffle::*,
 };
+
+#[cfg(all(feature = "std", unix))]
+#[doc(inline)]
+pub use self::random::*;
+
+/// Derives [`Rand`](trait.Rand.html) for a struct or enum.
+///
+/// Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use chance_derive::Rand;
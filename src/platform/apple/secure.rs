@@ -77,3 +77,11 @@ impl SecRandom {
 /// An error returned when [`SecRandom`](struct.SecRandom.html) fails.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SecRandomError(i32);
+
+impl SecRandomError {
+    /// Returns the `OSStatus` code returned by `SecRandomCopyBytes`.
+    #[inline]
+    pub const fn code(&self) -> i32 {
+        self.0
+    }
+}
@@ -0,0 +1,264 @@
+#![allow(non_camel_case_types)]
+
+#[cfg(feature = "std")]
+use std::{fs::File, io::{self, Read}};
+use crate::prelude::*;
+
+type c_int = i32;
+type c_uint = u32;
+type c_long = isize;
+
+const EINTR: c_int = 4;
+#[cfg(feature = "std")]
+const EIO: c_int = 5;
+#[cfg(not(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6",
+    target_arch = "sparc",
+    target_arch = "sparc64",
+)))]
+const ENOSYS: c_int = 38;
+
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6",
+))]
+const ENOSYS: c_int = 89;
+
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+const ENOSYS: c_int = 90;
+
+/// The `getrandom` system call number, or `None` if it's unknown for the target
+/// architecture, in which case the call is treated as unsupported.
+#[cfg(target_arch = "x86_64")]
+const SYS_GETRANDOM: Option<c_long> = Some(318);
+
+#[cfg(target_arch = "x86")]
+const SYS_GETRANDOM: Option<c_long> = Some(355);
+
+#[cfg(any(
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "riscv32",
+    target_arch = "loongarch64",
+))]
+const SYS_GETRANDOM: Option<c_long> = Some(278);
+
+#[cfg(target_arch = "arm")]
+const SYS_GETRANDOM: Option<c_long> = Some(384);
+
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+const SYS_GETRANDOM: Option<c_long> = Some(359);
+
+#[cfg(target_arch = "s390x")]
+const SYS_GETRANDOM: Option<c_long> = Some(349);
+
+#[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+const SYS_GETRANDOM: Option<c_long> = Some(347);
+
+// The o32 ABI
+#[cfg(any(target_arch = "mips", target_arch = "mips32r6"))]
+const SYS_GETRANDOM: Option<c_long> = Some(4353);
+
+// The n64 ABI
+#[cfg(all(
+    any(target_arch = "mips64", target_arch = "mips64r6"),
+    target_pointer_width = "64",
+))]
+const SYS_GETRANDOM: Option<c_long> = Some(5313);
+
+// The n32 ABI
+#[cfg(all(
+    any(target_arch = "mips64", target_arch = "mips64r6"),
+    target_pointer_width = "32",
+))]
+const SYS_GETRANDOM: Option<c_long> = Some(6317);
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "riscv32",
+    target_arch = "loongarch64",
+    target_arch = "arm",
+    target_arch = "powerpc",
+    target_arch = "powerpc64",
+    target_arch = "s390x",
+    target_arch = "sparc",
+    target_arch = "sparc64",
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6",
+)))]
+const SYS_GETRANDOM: Option<c_long> = None;
+
+extern "C" {
+    fn syscall(number: c_long, ...) -> c_long;
+    fn __errno_location() -> *mut c_int;
+}
+
+/// Makes `getrandom` return `EAGAIN` instead of blocking when the entropy pool
+/// has not yet been initialized.
+pub const GRND_NONBLOCK: u32 = 0x0001;
+
+/// Makes `getrandom` draw from the `/dev/random` pool instead of the
+/// `/dev/urandom` pool.
+pub const GRND_RANDOM: u32 = 0x0002;
+
+/// A cryptographically secure random number generator that uses the
+/// `getrandom` system call.
+///
+/// Unlike [`DevURandom`](../unix/struct.DevURandom.html), this does not need a
+/// file descriptor or access to `/dev`, and it blocks until the kernel's
+/// entropy pool has been initialized (unless [`GRND_NONBLOCK`] is set).
+///
+/// When the `std` feature is enabled and the kernel does not support
+/// `getrandom` (`ENOSYS`), this falls back to reading from `/dev/urandom`, or
+/// from `/dev/random` if [`GRND_RANDOM`] is set.
+///
+/// # Availability
+///
+/// - Linux 3.17+
+///
+/// [`GRND_NONBLOCK`]: constant.GRND_NONBLOCK.html
+/// [`GRND_RANDOM`]: constant.GRND_RANDOM.html
+#[derive(Debug)]
+pub struct GetRandom {
+    flags: c_uint,
+    #[cfg(feature = "std")]
+    fallback: Option<File>,
+}
+
+impl Default for GetRandom {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GetRandom {
+    /// Creates a new instance that calls `getrandom` without any flags.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_flags(0)
+    }
+
+    /// Creates a new instance that calls `getrandom` with `flags`, which may be
+    /// any combination of [`GRND_NONBLOCK`] and [`GRND_RANDOM`].
+    ///
+    /// [`GRND_NONBLOCK`]: constant.GRND_NONBLOCK.html
+    /// [`GRND_RANDOM`]: constant.GRND_RANDOM.html
+    #[inline]
+    pub const fn with_flags(flags: u32) -> Self {
+        GetRandom {
+            flags,
+            #[cfg(feature = "std")]
+            fallback: None,
+        }
+    }
+
+    /// Returns the flags passed to `getrandom`.
+    #[inline]
+    pub const fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    #[cfg(feature = "std")]
+    fn fill_via_fallback(&mut self, buf: &mut [u8]) -> Result<(), GetRandomError> {
+        fn code(err: io::Error) -> GetRandomError {
+            GetRandomError(err.raw_os_error().unwrap_or(EIO))
+        }
+
+        let file = match &mut self.fallback {
+            Some(file) => file,
+            fallback @ None => {
+                let path = if self.flags & GRND_RANDOM != 0 {
+                    "/dev/random"
+                } else {
+                    "/dev/urandom"
+                };
+                fallback.get_or_insert(File::open(path).map_err(code)?)
+            },
+        };
+        file.read_exact(buf).map_err(code)
+    }
+}
+
+impl TryRng for GetRandom {
+    type Error = GetRandomError;
+
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        #[cfg(feature = "std")]
+        {
+            if self.fallback.is_some() {
+                return self.fill_via_fallback(buf);
+            }
+        }
+
+        match sys_getrandom(buf, self.flags) {
+            #[cfg(feature = "std")]
+            Err(err) if err.is_unsupported() => self.fill_via_fallback(buf),
+            result => result,
+        }
+    }
+}
+
+impl CryptoRng for GetRandom {}
+
+/// Fills `buf` using only the `getrandom` system call, returning `ENOSYS` if
+/// either the kernel or the target architecture does not support it.
+pub(crate) fn sys_getrandom(buf: &mut [u8], flags: c_uint) -> Result<(), GetRandomError> {
+    let number = match SYS_GETRANDOM {
+        Some(number) => number,
+        None => return Err(GetRandomError(ENOSYS)),
+    };
+
+    let mut rem = buf;
+    while !rem.is_empty() {
+        let ret = unsafe {
+            syscall(number, rem.as_mut_ptr(), rem.len(), flags)
+        };
+        if ret < 0 {
+            match unsafe { *__errno_location() } {
+                EINTR => continue,
+                err => return Err(GetRandomError(err)),
+            }
+        }
+        // Reads may be partial for large buffers or if interrupted
+        rem = &mut rem[ret as usize..];
+    }
+    Ok(())
+}
+
+/// An error returned when [`GetRandom`](struct.GetRandom.html) fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetRandomError(i32);
+
+impl GetRandomError {
+    /// Returns the `errno` value reported by the operating system.
+    #[inline]
+    pub const fn raw_os_error(&self) -> i32 {
+        self.0
+    }
+
+    /// Returns whether the kernel does not support `getrandom` and no fallback
+    /// was available.
+    #[inline]
+    pub const fn is_unsupported(&self) -> bool {
+        self.0 == ENOSYS
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<GetRandomError> for io::Error {
+    #[inline]
+    fn from(err: GetRandomError) -> Self {
+        io::Error::from_raw_os_error(err.0)
+    }
+}
@@ -0,0 +1,4 @@
+//! Linux-specific functionality.
+
+mod getrandom;
+pub use self::getrandom::*;
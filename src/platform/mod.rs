@@ -3,5 +3,8 @@
 #[cfg(target_vendor = "apple")]
 pub mod apple;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+
 #[cfg(unix)]
 pub mod unix;
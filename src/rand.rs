@@ -1,6 +1,22 @@
 //! Generate random values.
 
-use crate::prelude::*;
+use core::{mem::{self, MaybeUninit}, ptr};
+use core::num::{
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    Wrapping,
+};
+use crate::{prelude::*, float::FloatBits, rand_in::SampleBelow};
 
 /// A type that can be generated from an `Rng` or `TryRng`.
 pub trait Rand: Sized {
@@ -23,6 +39,55 @@ pub trait Rand: Sized {
         *self = Self::try_rand(rng)?;
         Ok(())
     }
+
+    /// Generates an array of random instances of `self` without failing.
+    ///
+    /// This is what implements [`Rand`](trait.Rand.html) for `[Self; N]`, and
+    /// may be overridden to generate the array faster than one element at a
+    /// time.
+    #[inline]
+    fn rand_array<R: ?Sized + Rng, const N: usize>(rng: &mut R) -> [Self; N] {
+        match Self::try_rand_array(rng) {
+            Ok(array) => array,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Generates an array of random instances of `self`, returning an error
+    /// upon failure.
+    ///
+    /// This is what implements [`Rand`](trait.Rand.html) for `[Self; N]`, and
+    /// may be overridden to generate the array faster than one element at a
+    /// time.
+    fn try_rand_array<R: ?Sized + TryRng, const N: usize>(rng: &mut R) -> Result<[Self; N], R::Error> {
+        /// Drops the initialized prefix of `array` if generation fails.
+        struct Guard<'a, T, const N: usize> {
+            array: &'a mut [MaybeUninit<T>; N],
+            len: usize,
+        }
+
+        impl<T, const N: usize> Drop for Guard<'_, T, N> {
+            fn drop(&mut self) {
+                for value in &mut self.array[..self.len] {
+                    unsafe { ptr::drop_in_place(value.as_mut_ptr()) };
+                }
+            }
+        }
+
+        // Safe because an array of `MaybeUninit` does not need initialization
+        let mut array: [MaybeUninit<Self>; N] = unsafe {
+            MaybeUninit::uninit().assume_init()
+        };
+        let mut guard = Guard { array: &mut array, len: 0 };
+        while guard.len < N {
+            guard.array[guard.len] = MaybeUninit::new(Self::try_rand(rng)?);
+            guard.len += 1;
+        }
+        mem::forget(guard);
+
+        // Safe because every element has been initialized
+        Ok(unsafe { (&array as *const [MaybeUninit<Self>; N] as *const [Self; N]).read() })
+    }
 }
 
 impl Rand for u8 {
@@ -35,6 +100,20 @@ impl Rand for u8 {
     fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
         rng.try_next_u8()
     }
+
+    #[inline]
+    fn rand_array<R: ?Sized + Rng, const N: usize>(rng: &mut R) -> [Self; N] {
+        let mut array = [0; N];
+        rng.fill_bytes(&mut array);
+        array
+    }
+
+    #[inline]
+    fn try_rand_array<R: ?Sized + TryRng, const N: usize>(rng: &mut R) -> Result<[Self; N], R::Error> {
+        let mut array = [0; N];
+        rng.try_fill_bytes(&mut array)?;
+        Ok(array)
+    }
 }
 
 impl Rand for u16 {
@@ -110,3 +189,219 @@ impl Rand for usize {
         { rng.try_next_u64().map(|x| x as _) }
     }
 }
+
+macro_rules! impl_signed {
+    ($($int:ty => $uint:ty),+) => { $(
+        impl Rand for $int {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                <$uint>::rand(rng) as $int
+            }
+
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                <$uint>::try_rand(rng).map(|x| x as $int)
+            }
+        }
+    )+ }
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+macro_rules! impl_non_zero {
+    ($($nz:ty => $int:ty),+) => { $(
+        impl Rand for $nz {
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                match Self::try_rand(rng) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                // Reject zero rather than remapping it to keep uniformity
+                loop {
+                    if let Some(value) = <$nz>::new(<$int>::try_rand(rng)?) {
+                        return Ok(value);
+                    }
+                }
+            }
+        }
+    )+ }
+}
+
+impl_non_zero! {
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+}
+
+impl<T: Rand> Rand for Wrapping<T> {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        Wrapping(T::rand(rng))
+    }
+
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        T::try_rand(rng).map(Wrapping)
+    }
+}
+
+impl Rand for bool {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        rng.next_u8() & 1 != 0
+    }
+
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        rng.try_next_u8().map(|x| x & 1 != 0)
+    }
+}
+
+impl Rand for char {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        match Self::try_rand(rng) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        // Sample uniformly among scalar values, skipping over surrogates
+        const SURROGATE_START: u32 = 0xD800;
+        const SURROGATE_LEN: u32 = 0x800;
+        const SCALAR_COUNT: u32 = char::MAX as u32 + 1 - SURROGATE_LEN;
+
+        let mut value = u32::try_sample_below(rng, SCALAR_COUNT)?;
+        if value >= SURROGATE_START {
+            value += SURROGATE_LEN;
+        }
+        // Safe because surrogates were skipped and `value <= char::MAX`
+        Ok(unsafe { char::from_u32_unchecked(value) })
+    }
+}
+
+macro_rules! impl_float {
+    ($($float:ty, $next:ident, $try_next:ident;)+) => { $(
+        impl Rand for $float {
+            /// Generates a random value in `[0, 1)` without fail.
+            #[inline]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                Self::closed_open(rng.$next())
+            }
+
+            /// Generates a random value in `[0, 1)`, returning an error upon
+            /// failure.
+            #[inline]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                rng.$try_next().map(Self::closed_open)
+            }
+        }
+    )+ }
+}
+
+impl_float! {
+    f32, next_u32, try_next_u32;
+    f64, next_u64, try_next_u64;
+}
+
+impl<T: Rand, const N: usize> Rand for [T; N] {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        T::rand_array(rng)
+    }
+
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        T::try_rand_array(rng)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($t:ident),*);)+) => { $(
+        impl<$($t: Rand),*> Rand for ($($t,)*) {
+            #[inline]
+            #[allow(unused_variables, clippy::unused_unit)]
+            fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+                ($($t::rand(rng),)*)
+            }
+
+            #[inline]
+            #[allow(unused_variables, clippy::unused_unit)]
+            fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+                Ok(($($t::try_rand(rng)?,)*))
+            }
+        }
+    )+ }
+}
+
+impl_tuple! {
+    ();
+    (A);
+    (A, B);
+    (A, B, C);
+    (A, B, C, D);
+    (A, B, C, D, E);
+    (A, B, C, D, E, F);
+    (A, B, C, D, E, F, G);
+    (A, B, C, D, E, F, G, H);
+    (A, B, C, D, E, F, G, H, I);
+    (A, B, C, D, E, F, G, H, I, J);
+    (A, B, C, D, E, F, G, H, I, J, K);
+    (A, B, C, D, E, F, G, H, I, J, K, L);
+}
+
+impl<T: Rand> Rand for Option<T> {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        match Self::try_rand(rng) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Generates `None` or `Some` with equal probability.
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        if bool::try_rand(rng)? {
+            T::try_rand(rng).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: Rand, E: Rand> Rand for Result<T, E> {
+    #[inline]
+    fn rand<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        match Self::try_rand(rng) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Generates `Ok` or `Err` with equal probability.
+    #[inline]
+    fn try_rand<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        if bool::try_rand(rng)? {
+            T::try_rand(rng).map(Ok)
+        } else {
+            E::try_rand(rng).map(Err)
+        }
+    }
+}
@@ -1,5 +1,12 @@
-use core::ops::Range;
-use crate::prelude::*;
+use core::ops::{
+    Range,
+    RangeFrom,
+    RangeFull,
+    RangeInclusive,
+    RangeTo,
+    RangeToInclusive,
+};
+use crate::{prelude::*, float::Closed01};
 
 /// A type that can have a random instance retrieved from an instance of `A`.
 pub trait RandIn<A>: Sized {
@@ -14,11 +21,19 @@ pub trait RandIn<A>: Sized {
     /// Creates an instance of `self` from within `value` using `rng` without
     /// fail and without checking if `value` is empty or if `rng` returned a
     /// valid value that can be used to fetch from `value`.
+    ///
+    /// # Safety
+    ///
+    /// `value` must not be empty.
     unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, value: A) -> Self;
 
     /// Creates an instance of `self` from within `value` using `rng` without
     /// checking if `value` is empty or if `rng` returned a valid value that can
     /// be used to fetch from `value`, but returning `Err` if `rng` failed.
+    ///
+    /// # Safety
+    ///
+    /// `value` must not be empty.
     unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, value: A) -> Result<Self, R::Error>;
 }
 
@@ -90,57 +105,492 @@ impl<'a, A> RandIn<&'a mut [A]> for &'a mut A {
     }
 }
 
-macro_rules! impl_i {
-    ($($int:ty),+) => { $(
-        impl RandIn<Range<$int>> for $int {
+/// Unsigned integers that support widening multiplication.
+pub(crate) trait WideMul: Sized {
+    /// Returns the high and low halves of the double-width product of `self`
+    /// and `other`.
+    fn wmul(self, other: Self) -> (Self, Self);
+}
+
+macro_rules! impl_wide_mul {
+    ($($uint:ty => $wide:ty),+) => { $(
+        impl WideMul for $uint {
+            #[inline]
+            fn wmul(self, other: Self) -> (Self, Self) {
+                let m = self as $wide * other as $wide;
+                ((m >> <$uint>::BITS) as $uint, m as $uint)
+            }
+        }
+    )+ }
+}
+
+impl_wide_mul!(u8 => u16, u16 => u32, u32 => u64, u64 => u128);
+
+impl WideMul for u128 {
+    #[inline]
+    fn wmul(self, other: Self) -> (Self, Self) {
+        const LOWER: u128 = u64::MAX as u128;
+        let (a_lo, a_hi) = (self & LOWER, self >> 64);
+        let (b_lo, b_hi) = (other & LOWER, other >> 64);
+
+        let ll = a_lo * b_lo;
+        let lh = a_lo * b_hi;
+        let hl = a_hi * b_lo;
+        let hh = a_hi * b_hi;
+
+        // Cannot overflow: each term is less than 2^64
+        let mid = (ll >> 64) + (lh & LOWER) + (hl & LOWER);
+
+        let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+        let lo = (ll & LOWER) | (mid << 64);
+        (hi, lo)
+    }
+}
+
+/// Unsigned integers that can be sampled uniformly below an upper bound.
+pub(crate) trait SampleBelow: Sized {
+    /// Returns a uniformly distributed value in `0..bound` using `rng`, where a
+    /// `bound` of 0 denotes the entire range of `Self`.
+    ///
+    /// This uses Lemire's widening multiply method, which rejects values from
+    /// the biased zone rather than relying on `%`.
+    fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, bound: Self) -> Result<Self, R::Error>;
+}
+
+macro_rules! impl_sample_below {
+    ($($uint:ty),+) => { $(
+        impl SampleBelow for $uint {
+            #[inline]
+            fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, bound: Self) -> Result<Self, R::Error> {
+                if bound == 0 {
+                    return <$uint>::try_rand(rng);
+                }
+                let (mut hi, mut lo) = <$uint>::try_rand(rng)?.wmul(bound);
+                if lo < bound {
+                    // Equal to `(2^BITS - bound) % bound`
+                    let threshold = bound.wrapping_neg() % bound;
+                    while lo < threshold {
+                        let (h, l) = <$uint>::try_rand(rng)?.wmul(bound);
+                        hi = h;
+                        lo = l;
+                    }
+                }
+                Ok(hi)
+            }
+        }
+    )+ }
+}
+
+impl_sample_below!(u8, u16, u32, u64, u128);
+
+#[cfg(target_pointer_width = "16")]
+type FixedUsize = u16;
+
+#[cfg(target_pointer_width = "32")]
+type FixedUsize = u32;
+
+#[cfg(target_pointer_width = "64")]
+type FixedUsize = u64;
+
+impl WideMul for usize {
+    #[inline]
+    fn wmul(self, other: Self) -> (Self, Self) {
+        let (hi, lo) = (self as FixedUsize).wmul(other as FixedUsize);
+        (hi as usize, lo as usize)
+    }
+}
+
+impl SampleBelow for usize {
+    #[inline]
+    fn try_sample_below<R: ?Sized + TryRng>(rng: &mut R, bound: Self) -> Result<Self, R::Error> {
+        FixedUsize::try_sample_below(rng, bound as FixedUsize).map(|x| x as usize)
+    }
+}
+
+/// Integer ranges that can be reduced to inclusive bounds.
+trait InclusiveBounds<T> {
+    /// Returns the smallest and largest values within `self`, or `None` if
+    /// `self` is empty.
+    fn inclusive_bounds(&self) -> Option<(T, T)>;
+
+    /// Returns the smallest and largest values within `self` without checking
+    /// whether `self` is empty.
+    fn inclusive_bounds_unchecked(&self) -> (T, T);
+}
+
+macro_rules! impl_rand_in {
+    ($int:ty => $uint:ty; $($range:ty),+) => { $(
+        impl RandIn<$range> for $int {
+            #[inline]
+            fn rand_in<R: ?Sized + Rng>(rng: &mut R, range: $range) -> Option<Self> {
+                if InclusiveBounds::<$int>::inclusive_bounds(&range).is_some() {
+                    unsafe { Some(Self::rand_in_unchecked(rng, range)) }
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn try_rand_in<R: ?Sized + TryRng>(rng: &mut R, range: $range) -> Result<Option<Self>, R::Error> {
+                if InclusiveBounds::<$int>::inclusive_bounds(&range).is_some() {
+                    unsafe { Self::try_rand_in_unchecked(rng, range).map(Some) }
+                } else {
+                    Ok(None)
+                }
+            }
+
+            #[inline]
+            unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, range: $range) -> Self {
+                match Self::try_rand_in_unchecked(rng, range) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, range: $range) -> Result<Self, R::Error> {
+                // Two's complement makes the distance between the bounds of
+                // signed ranges fit within the unsigned counterpart, and a
+                // bound that wraps to 0 denotes the entire range
+                let (low, high) = InclusiveBounds::<$int>::inclusive_bounds_unchecked(&range);
+                let low = low as $uint;
+                let bound = (high as $uint).wrapping_sub(low).wrapping_add(1);
+                let offset = <$uint>::try_sample_below(rng, bound)?;
+                Ok(low.wrapping_add(offset) as $int)
+            }
+        }
+    )+ }
+}
+
+macro_rules! impl_int {
+    ($($int:ty => $uint:ty),+) => { $(
+        impl InclusiveBounds<$int> for Range<$int> {
+            #[inline]
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                if self.start < self.end {
+                    Some(self.inclusive_bounds_unchecked())
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (self.start, self.end.wrapping_sub(1))
+            }
+        }
+
+        impl InclusiveBounds<$int> for RangeInclusive<$int> {
+            #[inline]
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                if self.is_empty() {
+                    None
+                } else {
+                    Some(self.inclusive_bounds_unchecked())
+                }
+            }
+
+            #[inline]
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (*self.start(), *self.end())
+            }
+        }
+
+        impl InclusiveBounds<$int> for RangeFrom<$int> {
+            #[inline]
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                Some(self.inclusive_bounds_unchecked())
+            }
+
+            #[inline]
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (self.start, <$int>::MAX)
+            }
+        }
+
+        impl InclusiveBounds<$int> for RangeTo<$int> {
             #[inline]
-            fn rand_in<R: ?Sized + Rng>(rng: &mut R, range: Range<$int>) -> Option<Self> {
-                unimplemented!()
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                if self.end > <$int>::MIN {
+                    Some(self.inclusive_bounds_unchecked())
+                } else {
+                    None
+                }
             }
 
             #[inline]
-            fn try_rand_in<R: ?Sized + TryRng>(rng: &mut R, range: Range<$int>) -> Result<Option<Self>, R::Error> {
-                unimplemented!()
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (<$int>::MIN, self.end.wrapping_sub(1))
             }
+        }
+
+        impl InclusiveBounds<$int> for RangeToInclusive<$int> {
+            #[inline]
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                Some(self.inclusive_bounds_unchecked())
+            }
+
+            #[inline]
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (<$int>::MIN, self.end)
+            }
+        }
 
+        impl InclusiveBounds<$int> for RangeFull {
             #[inline]
-            unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, range: Range<$int>) -> Self {
-                unimplemented!()
+            fn inclusive_bounds(&self) -> Option<($int, $int)> {
+                Some(self.inclusive_bounds_unchecked())
             }
 
             #[inline]
-            unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, range: Range<$int>) -> Result<Self, R::Error> {
-                unimplemented!()
+            fn inclusive_bounds_unchecked(&self) -> ($int, $int) {
+                (<$int>::MIN, <$int>::MAX)
             }
         }
+
+        impl_rand_in! {
+            $int => $uint;
+            Range<$int>,
+            RangeInclusive<$int>,
+            RangeFrom<$int>,
+            RangeTo<$int>,
+            RangeToInclusive<$int>,
+            RangeFull
+        }
     )+ }
 }
 
-macro_rules! impl_u {
-    ($($int:ty),+) => { $(
-        impl RandIn<Range<$int>> for $int {
+impl_int! {
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize,
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize
+}
+
+/// Linear interpolation between float bounds.
+pub(crate) trait Lerp: Copy {
+    /// Returns `start + (end - start) * t` while avoiding overflow when the
+    /// distance between `start` and `end` is not finite.
+    fn lerp(start: Self, end: Self, t: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($($float:ident),+) => { $(
+        impl RandIn<Range<$float>> for $float {
+            /// Generates a value uniformly in `range` without fail, or returns
+            /// `None` if `range` is empty or either bound is not finite.
+            ///
+            /// The result is never equal to `range.end`, even when rounding
+            /// would otherwise produce it.
+            #[inline]
+            fn rand_in<R: ?Sized + Rng>(rng: &mut R, range: Range<$float>) -> Option<Self> {
+                match Self::try_rand_in(rng, range) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            fn try_rand_in<R: ?Sized + TryRng>(rng: &mut R, range: Range<$float>) -> Result<Option<Self>, R::Error> {
+                let Range { start, end } = range;
+                if start < end && start.is_finite() && end.is_finite() {
+                    unsafe { Self::try_rand_in_unchecked(rng, range).map(Some) }
+                } else {
+                    Ok(None)
+                }
+            }
+
+            #[inline]
+            unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, range: Range<$float>) -> Self {
+                match Self::try_rand_in_unchecked(rng, range) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
+            }
+
+            #[inline]
+            unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, range: Range<$float>) -> Result<Self, R::Error> {
+                let Range { start, end } = range;
+                loop {
+                    let value = Lerp::lerp(start, end, $float::try_rand(rng)?);
+
+                    // Rounding can make `value` equal to `end`, in which case
+                    // rejecting it keeps the rest of the range uniform
+                    if value < end {
+                        return Ok(value);
+                    }
+                }
+            }
+        }
+
+        impl RandIn<RangeInclusive<$float>> for $float {
+            /// Generates a value uniformly in `range` without fail, or returns
+            /// `None` if `range` is empty or either bound is not finite.
             #[inline]
-            fn rand_in<R: ?Sized + Rng>(rng: &mut R, range: Range<$int>) -> Option<Self> {
-                unimplemented!()
+            fn rand_in<R: ?Sized + Rng>(rng: &mut R, range: RangeInclusive<$float>) -> Option<Self> {
+                match Self::try_rand_in(rng, range) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
             }
 
             #[inline]
-            fn try_rand_in<R: ?Sized + TryRng>(rng: &mut R, range: Range<$int>) -> Result<Option<Self>, R::Error> {
-                unimplemented!()
+            fn try_rand_in<R: ?Sized + TryRng>(rng: &mut R, range: RangeInclusive<$float>) -> Result<Option<Self>, R::Error> {
+                let (start, end) = (*range.start(), *range.end());
+                if start <= end && start.is_finite() && end.is_finite() {
+                    unsafe { Self::try_rand_in_unchecked(rng, range).map(Some) }
+                } else {
+                    Ok(None)
+                }
             }
 
             #[inline]
-            unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, range: Range<$int>) -> Self {
-                unimplemented!()
+            unsafe fn rand_in_unchecked<R: ?Sized + Rng>(rng: &mut R, range: RangeInclusive<$float>) -> Self {
+                match Self::try_rand_in_unchecked(rng, range) {
+                    Ok(value) => value,
+                    Err(err) => match err {},
+                }
             }
 
             #[inline]
-            unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, range: Range<$int>) -> Result<Self, R::Error> {
-                unimplemented!()
+            unsafe fn try_rand_in_unchecked<R: ?Sized + TryRng>(rng: &mut R, range: RangeInclusive<$float>) -> Result<Self, R::Error> {
+                let (start, end) = (*range.start(), *range.end());
+                let Closed01(t) = Closed01::<$float>::try_rand(rng)?;
+
+                // Rounding can overshoot `end`
+                Ok(Lerp::lerp(start, end, t).min(end))
+            }
+        }
+
+        impl Lerp for $float {
+            #[inline]
+            fn lerp(start: Self, end: Self, t: Self) -> Self {
+                let scale = end - start;
+                if scale.is_finite() {
+                    start + scale * t
+                } else {
+                    // Work at half scale to keep intermediate values finite
+                    let (start, end) = (start * 0.5, end * 0.5);
+                    (start + (end - start) * t) * 2.0
+                }
             }
         }
     )+ }
 }
 
-impl_i!(i8, i16, i32, i64, i128, isize);
-impl_u!(u8, u16, u32, u64, u128, usize);
+impl_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_util::{assert_chi_squared, rng}};
+
+    #[test]
+    fn small_signed_range_is_uniform() {
+        let mut rng = rng(1);
+        let mut counts = [0u64; 7];
+        for _ in 0..700_000 {
+            let x = i8::rand_in(&mut rng, -3..4).unwrap();
+            counts[(x + 3) as usize] += 1;
+        }
+        assert_chi_squared(&counts, &[1.0; 7]);
+    }
+
+    #[test]
+    fn range_with_large_rejection_zone_is_uniform() {
+        // `256 % 171 = 85`, so a modulo reduction would favor the lower half
+        let mut rng = rng(2);
+        let mut counts = [0u64; 171];
+        for _ in 0..1_710_000 {
+            counts[u8::rand_in(&mut rng, 0..171).unwrap() as usize] += 1;
+        }
+        assert_chi_squared(&counts, &[1.0; 171]);
+    }
+
+    #[test]
+    fn top_of_u128_range_is_uniform() {
+        let mut rng = rng(3);
+        let mut counts = [0u64; 6];
+        for _ in 0..600_000 {
+            let x = u128::rand_in(&mut rng, u128::MAX - 5..=u128::MAX).unwrap();
+            counts[(x - (u128::MAX - 5)) as usize] += 1;
+        }
+        assert_chi_squared(&counts, &[1.0; 6]);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn full_and_empty_ranges() {
+        let mut rng = rng(4);
+        assert_eq!(u8::rand_in(&mut rng, 5..5), None);
+        assert_eq!(i32::rand_in(&mut rng, 3..=2), None);
+        assert_eq!(u64::rand_in(&mut rng, 7..=7), Some(7));
+        assert!(i128::rand_in(&mut rng, ..).is_some());
+    }
+
+    macro_rules! float_tests {
+        ($($float:ident, $mod:ident;)+) => { $(
+            mod $mod {
+                use super::*;
+
+                fn next_up(x: $float) -> $float {
+                    if x < 0.0 {
+                        $float::from_bits(x.to_bits() - 1)
+                    } else {
+                        $float::from_bits(x.to_bits() + 1)
+                    }
+                }
+
+                #[test]
+                fn single_step_ranges() {
+                    let mut rng = rng(5);
+                    for &x in &[0.0, 1.0, -1.0, 1e-30, $float::MIN_POSITIVE, $float::MAX / 2.0] {
+                        let next = next_up(x);
+                        for _ in 0..100 {
+                            assert_eq!($float::rand_in(&mut rng, x..next), Some(x));
+                        }
+
+                        let mut seen = [false; 2];
+                        for _ in 0..1000 {
+                            let value = $float::rand_in(&mut rng, x..=next).unwrap();
+                            assert!(value == x || value == next);
+                            seen[(value == next) as usize] = true;
+                        }
+                        assert_eq!(seen, [true; 2]);
+                    }
+                }
+
+                #[test]
+                fn full_finite_range() {
+                    let mut rng = rng(6);
+                    let mut counts = [0u64; 10];
+                    for _ in 0..100_000 {
+                        let value = $float::rand_in(&mut rng, $float::MIN..$float::MAX).unwrap();
+                        assert!(value.is_finite() && value < $float::MAX);
+                        let bin = ((value / $float::MAX + 1.0) * 5.0) as usize;
+                        counts[bin.min(9)] += 1;
+                    }
+                    assert_chi_squared(&counts, &[1.0; 10]);
+
+                    let value = $float::rand_in(&mut rng, $float::MIN..=$float::MAX).unwrap();
+                    assert!(value.is_finite());
+                }
+
+                #[test]
+                #[allow(clippy::reversed_empty_ranges)]
+                fn bad_ranges() {
+                    let mut rng = rng(7);
+                    let (nan, inf) = ($float::NAN, $float::INFINITY);
+                    for &(start, end) in &[(nan, 1.0), (0.0, nan), (-inf, 0.0), (0.0, inf), (-inf, inf), (2.0, 1.0)] {
+                        assert_eq!($float::rand_in(&mut rng, start..end), None);
+                        assert_eq!($float::rand_in(&mut rng, start..=end), None);
+                    }
+                    assert_eq!($float::rand_in(&mut rng, 1.0..1.0), None);
+                    assert_eq!($float::rand_in(&mut rng, 1.0..=1.0), Some(1.0));
+                }
+            }
+        )+ }
+    }
+
+    float_tests! {
+        f32, f32_ranges;
+        f64, f64_ranges;
+    }
+}
@@ -0,0 +1,20 @@
+//! Generate random values from the thread-local generator.
+
+use crate::{prelude::*, rng::thread_rng};
+
+/// Generates a random value of type `T` using [`thread_rng`].
+///
+/// [`thread_rng`]: rng/fn.thread_rng.html
+#[inline]
+pub fn random<T: Rand>() -> T {
+    T::rand(&mut thread_rng())
+}
+
+/// Generates a random value of type `T` from within `value` using
+/// [`thread_rng`], or returns `None` if `value` is empty.
+///
+/// [`thread_rng`]: rng/fn.thread_rng.html
+#[inline]
+pub fn random_in<T: RandIn<A>, A>(value: A) -> Option<T> {
+    T::rand_in(&mut thread_rng(), value)
+}
@@ -0,0 +1,283 @@
+//! ChaCha random number generators.
+//!
+//! These are cryptographically secure generators built on the ChaCha stream
+//! cipher designed by Daniel J. Bernstein. Each generator produces the cipher's
+//! keystream for a 256-bit key and a 64-bit stream ID, using a 64-bit block
+//! counter as described in the [original design](https://cr.yp.to/chacha.html).
+//!
+//! They are meant to be seeded once from a secure source provided by the
+//! operating system, and then used to generate numbers quickly in userspace.
+
+use core::fmt;
+use crate::prelude::*;
+
+/// The number of `u32` words in a block.
+const BLOCK_WORDS: usize = 16;
+
+/// "expand 32-byte k"
+const CONSTANTS: [u32; 4] = [0x6170_7865, 0x3320_646E, 0x7962_2D32, 0x6B20_6574];
+
+/// The mask for the 68-bit word position: a 64-bit block counter and a 4-bit
+/// index into the block.
+const WORD_POS_MASK: u128 = (1 << 68) - 1;
+
+#[inline(always)]
+fn quarter_round(x: &mut [u32; BLOCK_WORDS], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+/// The state shared by all ChaCha generators.
+#[derive(Clone, PartialEq, Eq)]
+struct Core {
+    key: [u32; 8],
+    stream: u64,
+    block: u64,
+    index: usize,
+    buffer: [u32; BLOCK_WORDS],
+}
+
+impl Core {
+    #[inline]
+    fn new(seed: [u8; 32], rounds: usize) -> Self {
+        let mut key = [0; 8];
+        for (word, chunk) in key.iter_mut().zip(seed.chunks_exact(4)) {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(chunk);
+            *word = u32::from_le_bytes(bytes);
+        }
+        let mut core = Core { key, stream: 0, block: 0, index: 0, buffer: [0; BLOCK_WORDS] };
+        core.refill(rounds);
+        core
+    }
+
+    /// Generates the keystream block for the current block counter.
+    fn refill(&mut self, rounds: usize) {
+        let k = &self.key;
+        let input = [
+            CONSTANTS[0], CONSTANTS[1], CONSTANTS[2], CONSTANTS[3],
+            k[0], k[1], k[2], k[3],
+            k[4], k[5], k[6], k[7],
+            self.block as u32, (self.block >> 32) as u32,
+            self.stream as u32, (self.stream >> 32) as u32,
+        ];
+
+        let mut x = input;
+        for _ in 0..rounds / 2 {
+            // Column round
+            quarter_round(&mut x, 0, 4, 8, 12);
+            quarter_round(&mut x, 1, 5, 9, 13);
+            quarter_round(&mut x, 2, 6, 10, 14);
+            quarter_round(&mut x, 3, 7, 11, 15);
+
+            // Diagonal round
+            quarter_round(&mut x, 0, 5, 10, 15);
+            quarter_round(&mut x, 1, 6, 11, 12);
+            quarter_round(&mut x, 2, 7, 8, 13);
+            quarter_round(&mut x, 3, 4, 9, 14);
+        }
+
+        for ((out, x), input) in self.buffer.iter_mut().zip(&x).zip(&input) {
+            *out = x.wrapping_add(*input);
+        }
+    }
+
+    #[inline]
+    fn next_u32(&mut self, rounds: usize) -> u32 {
+        if self.index >= BLOCK_WORDS {
+            self.block = self.block.wrapping_add(1);
+            self.index = 0;
+            self.refill(rounds);
+        }
+        let word = self.buffer[self.index];
+        self.index += 1;
+        word
+    }
+
+    #[inline]
+    fn word_pos(&self) -> u128 {
+        ((self.block as u128) << 4).wrapping_add(self.index as u128) & WORD_POS_MASK
+    }
+
+    #[inline]
+    fn set_word_pos(&mut self, pos: u128, rounds: usize) {
+        self.block = (pos >> 4) as u64;
+        self.index = (pos as usize) % BLOCK_WORDS;
+        self.refill(rounds);
+    }
+
+    #[inline]
+    fn set_stream(&mut self, stream: u64, rounds: usize) {
+        self.stream = stream;
+        self.refill(rounds);
+    }
+}
+
+macro_rules! imp {
+    ($($t:ident, $rounds:expr, $n:expr;)+) => { $(
+        /// A cryptographically secure random number generator that uses the
+        /// ChaCha stream cipher with
+        #[doc = $n]
+        /// rounds.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $t(Core);
+
+        impl fmt::Debug for $t {
+            // Do not leak the key or position through formatting
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(stringify!($t))
+            }
+        }
+
+        impl $t {
+            /// Returns the ID of the stream that `self` is generating.
+            #[inline]
+            pub fn get_stream(&self) -> u64 {
+                self.0.stream
+            }
+
+            /// Switches `self` to generating the stream with ID `stream`,
+            /// keeping the current word position.
+            ///
+            /// Each stream produces an independent sequence for the same seed.
+            #[inline]
+            pub fn set_stream(&mut self, stream: u64) {
+                self.0.set_stream(stream, $rounds);
+            }
+
+            /// Returns the index of the next 32-bit word that `self` will
+            /// generate within its stream.
+            ///
+            /// Only the lower 68 bits are used.
+            #[inline]
+            pub fn get_word_pos(&self) -> u128 {
+                self.0.word_pos()
+            }
+
+            /// Seeks to the 32-bit word at index `pos` within the stream.
+            ///
+            /// Only the lower 68 bits of `pos` are significant.
+            #[inline]
+            pub fn set_word_pos(&mut self, pos: u128) {
+                self.0.set_word_pos(pos, $rounds);
+            }
+        }
+
+        impl Rng for $t {
+            /// Fills `buf` with the keystream in little-endian byte order.
+            ///
+            /// A trailing partial word is discarded so that subsequent numbers
+            /// are generated from an aligned position.
+            #[inline]
+            fn fill_bytes(&mut self, buf: &mut [u8]) {
+                for chunk in buf.chunks_mut(4) {
+                    let word = self.next_u32().to_le_bytes();
+                    chunk.copy_from_slice(&word[..chunk.len()]);
+                }
+            }
+
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                self.0.next_u32($rounds)
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                let lo = self.next_u32() as u64;
+                let hi = self.next_u32() as u64;
+                (hi << 32) | lo
+            }
+        }
+
+        impl CryptoRng for $t {}
+
+        impl SeedableRng for $t {
+            type Seed = [u8; 32];
+
+            /// Creates a new instance with `seed` as the key, starting at the
+            /// beginning of stream 0.
+            #[inline]
+            fn from_seed(seed: [u8; 32]) -> Self {
+                $t(Core::new(seed, $rounds))
+            }
+        }
+    )+ }
+}
+
+imp! {
+    ChaCha8, 8, "8";
+    ChaCha12, 12, "12";
+    ChaCha20, 20, "20";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a hex string into bytes.
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn chacha20_reference() {
+        // RFC 8439, appendix A.1, test vectors 1 and 2: the all-zero key and
+        // nonce at block counters 0 and 1
+        let mut rng = ChaCha20::from_seed([0; 32]);
+        let mut keystream = [0u8; 128];
+        rng.fill_bytes(&mut keystream);
+        assert_eq!(&keystream[..], &hex(concat!(
+            "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7",
+            "da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+            "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed",
+            "29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f",
+        ))[..]);
+    }
+
+    #[test]
+    fn reduced_round_reference() {
+        // The all-zero key and IV from the ChaCha reference test vectors
+        let mut keystream = [0u8; 32];
+        ChaCha8::from_seed([0; 32]).fill_bytes(&mut keystream);
+        assert_eq!(&keystream[..], &hex("3e00ef2f895f40d67f5bb8e81f09a5a12c840ec3ce9a7f3b181be188ef711a1e")[..]);
+        ChaCha12::from_seed([0; 32]).fill_bytes(&mut keystream);
+        assert_eq!(&keystream[..], &hex("9bf49a6a0755f953811fce125f2683d50429c3bb49e074147e0089a52eae155f")[..]);
+    }
+
+    #[test]
+    fn word_pos_round_trip() {
+        let mut rng = ChaCha20::from_seed([7; 32]);
+        let words: Vec<u32> = (0..100).map(|_| rng.next_u32()).collect();
+        assert_eq!(rng.get_word_pos(), 100);
+
+        for &pos in &[0, 15, 16, 37, 99] {
+            rng.set_word_pos(pos);
+            assert_eq!(rng.get_word_pos(), pos);
+            assert_eq!(rng.next_u32(), words[pos as usize]);
+            assert_eq!(rng.get_word_pos(), pos + 1);
+        }
+
+        // Only the lower 68 bits are kept
+        let pos = (1 << 68) - 3;
+        rng.set_word_pos(pos);
+        assert_eq!(rng.get_word_pos(), pos);
+        rng.set_word_pos((1 << 68) + 5);
+        assert_eq!(rng.get_word_pos(), 5);
+        assert_eq!(rng.next_u32(), words[5]);
+    }
+
+    #[test]
+    fn streams_differ() {
+        let mut a = ChaCha12::from_seed([1; 32]);
+        let mut b = a.clone();
+        b.set_stream(1);
+        assert_eq!(b.get_stream(), 1);
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+}
@@ -5,9 +5,40 @@ use core::convert::Infallible;
 mod panic;
 pub use self::panic::PanickingRng;
 
+mod reseed;
+pub use self::reseed::ReseedingRng;
+
+mod seed;
+pub use self::seed::SeedableRng;
+
+#[cfg(any(
+    target_os = "linux",
+    target_vendor = "apple",
+    all(unix, feature = "std"),
+))]
+mod os;
+
+#[cfg(any(
+    target_os = "linux",
+    target_vendor = "apple",
+    all(unix, feature = "std"),
+))]
+pub use self::os::{OsRng, OsError};
+
+#[cfg(all(feature = "std", unix))]
+mod thread;
+
+#[cfg(all(feature = "std", unix))]
+pub use self::thread::{thread_rng, ThreadRng};
+
 #[cfg(feature = "std")]
 pub mod io;
 
+pub mod chacha;
+pub mod pcg;
+pub mod splitmix;
+pub mod xoshiro;
+
 /// Fills the contents of `buf` using `rng` without fail.
 #[inline]
 pub fn fill_bytes_via_next<R>(rng: &mut R, buf: &mut [u8])
@@ -156,7 +187,8 @@ impl<R: ?Sized + Rng> TryRng for R {
 
     #[inline]
     fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
-        Ok(self.fill_bytes(buf))
+        self.fill_bytes(buf);
+        Ok(())
     }
 
     #[inline]
@@ -185,6 +217,38 @@ impl<R: ?Sized + Rng> TryRng for R {
     }
 }
 
+impl<R: ?Sized + Rng> Rng for &mut R {
+    #[inline]
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        (**self).fill_bytes(buf);
+    }
+
+    #[inline]
+    fn next_u8(&mut self) -> u8 {
+        (**self).next_u8()
+    }
+
+    #[inline]
+    fn next_u16(&mut self) -> u16 {
+        (**self).next_u16()
+    }
+
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (**self).next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        (**self).next_u128()
+    }
+}
+
 // TODO: Figure out how to do the following without conflicting:
 // impl<R: ?Sized + TryRng> TryRng for &mut R
 
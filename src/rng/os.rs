@@ -0,0 +1,175 @@
+//! The operating system's random number generator.
+
+use crate::prelude::*;
+
+#[cfg(target_os = "linux")]
+use crate::platform::linux::{GetRandomError, sys_getrandom};
+
+#[cfg(target_vendor = "apple")]
+use crate::platform::apple::{SecRandom, SecRandomError};
+
+#[cfg(all(unix, feature = "std", not(target_vendor = "apple")))]
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether `getrandom` is known to be unsupported, so that the probe happens
+/// only once.
+#[cfg(all(target_os = "linux", feature = "std"))]
+static GETRANDOM_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(all(unix, feature = "std", not(target_vendor = "apple")))]
+mod urandom {
+    use core::{mem::ManuallyDrop, sync::atomic::{AtomicI32, Ordering}};
+    use std::{fs::File, io::{self, Read}, os::unix::io::{FromRawFd, IntoRawFd}};
+
+    /// The file descriptor of `/dev/urandom`, or -1 if it hasn't been opened.
+    static FD: AtomicI32 = AtomicI32::new(-1);
+
+    /// Fills `buf` from `/dev/urandom`, which is opened on first use and then
+    /// kept open for the lifetime of the process.
+    pub fn fill(buf: &mut [u8]) -> io::Result<()> {
+        let mut fd = FD.load(Ordering::Acquire);
+        if fd < 0 {
+            let new = File::open("/dev/urandom")?.into_raw_fd();
+            fd = match FD.compare_exchange(-1, new, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => new,
+                Err(existing) => {
+                    // Another thread opened it first
+                    drop(unsafe { File::from_raw_fd(new) });
+                    existing
+                },
+            };
+        }
+
+        let file = unsafe {
+            // Safe because `fd` stays open, and `ManuallyDrop` keeps it that
+            // way
+            ManuallyDrop::new(File::from_raw_fd(fd))
+        };
+        (&*file).read_exact(buf)
+    }
+}
+
+/// A cryptographically secure random number generator that uses the best
+/// entropy source provided by the operating system.
+///
+/// The source is selected at compile time:
+///
+/// | Platform    | Source                                                     |
+/// | ----------- | ---------------------------------------------------------- |
+/// | Linux       | `getrandom`, falling back to `/dev/urandom` with `std`     |
+/// | Apple       | `SecRandomCopyBytes`                                       |
+/// | Other Unix  | `/dev/urandom`, requires `std`                             |
+///
+/// Whether `getrandom` is supported is checked once per process, and
+/// `/dev/urandom` is opened at most once and then kept open.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OsRng(());
+
+impl OsRng {
+    /// Creates a new instance.
+    #[inline]
+    pub const fn new() -> Self {
+        OsRng(())
+    }
+}
+
+impl TryRng for OsRng {
+    type Error = OsError;
+
+    #[inline]
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), OsError> {
+        #[cfg(all(target_os = "linux", feature = "std"))]
+        {
+            if GETRANDOM_UNSUPPORTED.load(Ordering::Relaxed) {
+                return urandom::fill(buf).map_err(OsError::from);
+            }
+            match sys_getrandom(buf, 0) {
+                Err(err) if err.is_unsupported() => {
+                    GETRANDOM_UNSUPPORTED.store(true, Ordering::Relaxed);
+                    urandom::fill(buf).map_err(OsError::from)
+                },
+                result => result.map_err(OsError::from),
+            }
+        }
+
+        #[cfg(all(target_os = "linux", not(feature = "std")))]
+        { sys_getrandom(buf, 0).map_err(OsError::from) }
+
+        #[cfg(target_vendor = "apple")]
+        { SecRandom::new().try_fill_bytes(buf).map_err(OsError::from) }
+
+        #[cfg(all(unix, feature = "std", not(any(target_os = "linux", target_vendor = "apple"))))]
+        { urandom::fill(buf).map_err(OsError::from) }
+    }
+}
+
+impl CryptoRng for OsRng {}
+
+/// An error returned when [`OsRng`](struct.OsRng.html) fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OsError(i32);
+
+impl OsError {
+    /// Returns the error code reported by the operating system.
+    ///
+    /// This is an `errno` value on Linux and other Unix platforms, and an
+    /// `OSStatus` value on Apple platforms.
+    #[inline]
+    pub const fn raw_os_error(&self) -> i32 {
+        self.0
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<GetRandomError> for OsError {
+    #[inline]
+    fn from(err: GetRandomError) -> Self {
+        OsError(err.raw_os_error())
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+impl From<SecRandomError> for OsError {
+    #[inline]
+    fn from(err: SecRandomError) -> Self {
+        OsError(err.code())
+    }
+}
+
+#[cfg(all(unix, feature = "std", not(target_vendor = "apple")))]
+impl From<io::Error> for OsError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        // `EIO` for errors that did not originate from the OS, such as EOF
+        OsError(err.raw_os_error().unwrap_or(5))
+    }
+}
+
+#[cfg(all(feature = "std", not(target_vendor = "apple")))]
+impl From<OsError> for std::io::Error {
+    #[inline]
+    fn from(err: OsError) -> Self {
+        std::io::Error::from_raw_os_error(err.0)
+    }
+}
+
+#[cfg(all(test, unix, feature = "std", not(target_vendor = "apple")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_differ() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        OsRng::new().try_fill_bytes(&mut a).unwrap();
+        OsRng::new().try_fill_bytes(&mut b).unwrap();
+        assert_ne!(a, b);
+
+        urandom::fill(&mut a).unwrap();
+        urandom::fill(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+}
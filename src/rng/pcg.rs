@@ -0,0 +1,265 @@
+//! Permuted congruential generators.
+//!
+//! These are fast, non-cryptographic generators with small state, designed by
+//! Melissa O'Neill. Each generator has a selectable stream, which allows for
+//! many independent sequences to come from the same state. See
+//! [here](https://www.pcg-random.org) for details.
+
+use crate::prelude::*;
+
+const MULTIPLIER_64: u64 = 0x5851_F42D_4C95_7F2D;
+
+const MULTIPLIER_128: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645;
+
+/// The multiplier used by the "cheap multiplier" variants of 128-bit PCG.
+const CHEAP_MULTIPLIER_128: u64 = 0xDA94_2042_E4DD_58B5;
+
+macro_rules! imp {
+    ($($(#[$meta:meta])+ $t:ident, $state:ty, $mul:expr, $n:expr;)+) => { $(
+        $(#[$meta])+
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $t {
+            state: $state,
+            increment: $state,
+        }
+
+        impl $t {
+            /// Creates a new instance from `state` and the selected `stream`.
+            ///
+            /// Only the lower
+            #[doc = $n]
+            /// bits of `stream` are significant.
+            #[inline]
+            pub fn new(state: $state, stream: $state) -> Self {
+                // Ensure the increment is odd
+                let increment = (stream << 1) | 1;
+                let mut rng = $t { state: 0, increment };
+                rng.step();
+                rng.state = rng.state.wrapping_add(state);
+                rng.step();
+                rng
+            }
+
+            /// Advances `self` by `delta` steps in `O(log delta)` time.
+            ///
+            /// Because the period wraps around, moving backwards by `n` steps
+            /// can be done via `advance(n.wrapping_neg())`.
+            pub fn advance(&mut self, mut delta: $state) {
+                let mut acc_mul: $state = 1;
+                let mut acc_add: $state = 0;
+                let mut cur_mul: $state = $mul;
+                let mut cur_add = self.increment;
+
+                while delta > 0 {
+                    if delta & 1 != 0 {
+                        acc_mul = acc_mul.wrapping_mul(cur_mul);
+                        acc_add = acc_add.wrapping_mul(cur_mul).wrapping_add(cur_add);
+                    }
+                    cur_add = cur_mul.wrapping_add(1).wrapping_mul(cur_add);
+                    cur_mul = cur_mul.wrapping_mul(cur_mul);
+                    delta >>= 1;
+                }
+
+                self.state = acc_mul.wrapping_mul(self.state).wrapping_add(acc_add);
+            }
+
+            #[inline]
+            fn step(&mut self) {
+                self.state = self.state.wrapping_mul($mul).wrapping_add(self.increment);
+            }
+        }
+
+        impl SeedableRng for $t {
+            type Seed = [u8; 2 * core::mem::size_of::<$state>()];
+
+            /// Creates a new instance from `seed`, where the first half of
+            /// `seed` is the little-endian state and the second half is the
+            /// little-endian stream.
+            #[inline]
+            fn from_seed(seed: Self::Seed) -> Self {
+                const SIZE: usize = core::mem::size_of::<$state>();
+                let mut state = [0; SIZE];
+                let mut stream = [0; SIZE];
+                state.copy_from_slice(&seed[..SIZE]);
+                stream.copy_from_slice(&seed[SIZE..]);
+                Self::new(<$state>::from_le_bytes(state), <$state>::from_le_bytes(stream))
+            }
+        }
+    )+ }
+}
+
+imp! {
+    /// A PCG generator with 64 bits of state and 32-bit output via the XSH-RR
+    /// output function.
+    ///
+    /// This is the `pcg32` generator from the reference implementation.
+    Pcg32, u64, MULTIPLIER_64, "63";
+
+    /// A PCG generator with 128 bits of state and 64-bit output via the XSL-RR
+    /// output function.
+    ///
+    /// This is the `pcg64` generator from the reference implementation.
+    Pcg64, u128, MULTIPLIER_128, "127";
+
+    /// A PCG generator with 128 bits of state and 64-bit output via the DXSM
+    /// output function.
+    ///
+    /// This uses a cheaper 64-bit multiplier for its state transition and
+    /// has better statistical quality than [`Pcg64`](struct.Pcg64.html). It is
+    /// the `PCG64DXSM` generator found in NumPy.
+    Pcg64Dxsm, u128, CHEAP_MULTIPLIER_128 as u128, "127";
+}
+
+impl Rng for Pcg32 {
+    #[inline]
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        crate::rng::fill_bytes_via_next(self, buf);
+    }
+
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+
+        // XSH-RR: xorshift high bits, then random rotation
+        let rot = (state >> 59) as u32;
+        let xsh = (((state >> 18) ^ state) >> 27) as u32;
+        xsh.rotate_right(rot)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        let hi = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+}
+
+impl Rng for Pcg64 {
+    #[inline]
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        crate::rng::fill_bytes_via_next(self, buf);
+    }
+
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let state = self.state;
+
+        // XSL-RR: xorshift low bits, then random rotation
+        let rot = (state >> 122) as u32;
+        let xsl = ((state >> 64) as u64) ^ (state as u64);
+        xsl.rotate_right(rot)
+    }
+}
+
+impl Rng for Pcg64Dxsm {
+    #[inline]
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        crate::rng::fill_bytes_via_next(self, buf);
+    }
+
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let state = self.state;
+        self.step();
+
+        // DXSM: double xorshift multiply on the pre-iterated state
+        let mut hi = (state >> 64) as u64;
+        let lo = (state as u64) | 1;
+        hi ^= hi >> 32;
+        hi = hi.wrapping_mul(CHEAP_MULTIPLIER_128);
+        hi ^= hi >> 48;
+        hi.wrapping_mul(lo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Outputs of the reference implementations seeded with `(42, 54)`
+
+    #[test]
+    fn pcg32_reference() {
+        let mut rng = Pcg32::new(42, 54);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        for &value in &expected {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn pcg64_reference() {
+        let mut rng = Pcg64::new(42, 54);
+        let expected = [
+            0x86b1da1d72062b68,
+            0x1304aa46c9853d39,
+            0xa3670e9e0dd50358,
+            0xf9090e529a7dae00,
+            0xc85b9fd837996f2c,
+        ];
+        for &value in &expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    // Outputs of the reference `cm_setseq_dxsm_128_64` (NumPy's `PCG64DXSM`)
+    // seeded with `(42, 54)`, which uses the cheap multiplier for seeding too
+
+    #[test]
+    fn pcg64_dxsm_reference() {
+        let mut rng = Pcg64Dxsm::new(42, 54);
+        let expected = [
+            0xf0847c9518bddb90,
+            0x8e7d5f5514ba8aaa,
+            0x86fbd36f8028f6fd,
+            0x8d14b6edbe9f740a,
+            0xa85b2896c7cad55d,
+        ];
+        for &value in &expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn advance_matches_stepping() {
+        let mut stepped = Pcg32::new(1, 2);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u32();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+
+        let mut stepped = Pcg64::new(3, 4);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+
+        let mut stepped = Pcg64Dxsm::new(5, 6);
+        let mut advanced = stepped.clone();
+        for _ in 0..1000 {
+            stepped.next_u64();
+        }
+        advanced.advance(1000);
+        assert_eq!(advanced, stepped);
+
+        // Moving backwards undoes moving forwards
+        advanced.advance(1000u128.wrapping_neg());
+        assert_eq!(advanced, Pcg64Dxsm::new(5, 6));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::prelude::*;
+
+#[cfg(unix)]
+mod fork {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    extern "C" {
+        fn pthread_atfork(
+            prepare: Option<unsafe extern "C" fn()>,
+            parent: Option<unsafe extern "C" fn()>,
+            child: Option<unsafe extern "C" fn()>,
+        ) -> i32;
+    }
+
+    static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn bump_generation() {
+        // Only atomic operations are async-signal-safe in the child
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Ensures that forking bumps the generation counter.
+    #[inline]
+    pub fn register() {
+        if !REGISTERED.swap(true, Ordering::Relaxed) {
+            // The only possible error is `ENOMEM`, in which case forks simply
+            // go undetected
+            unsafe { pthread_atfork(None, None, Some(bump_generation)) };
+        }
+    }
+
+    /// Returns the number of times that the current process has been forked
+    /// from its ancestors since `register` was first called.
+    #[inline]
+    pub fn generation() -> usize {
+        GENERATION.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(not(unix))]
+mod fork {
+    #[inline]
+    pub fn register() {}
+
+    #[inline]
+    pub fn generation() -> usize {
+        0
+    }
+}
+
+/// A wrapper around a [`SeedableRng`](trait.SeedableRng.html) that reseeds it
+/// from another [`TryRng`](trait.TryRng.html).
+///
+/// Reseeding happens:
+///
+/// - After every `threshold` bytes of output, unless `threshold` is 0.
+///
+/// - After the process is forked, so that children do not produce the same
+///   sequence as their parent or siblings. This is detected on Unix platforms
+///   via `pthread_atfork`.
+///
+/// If reseeding fails, the error is returned and reseeding is attempted again
+/// on the next call. An [`Rng`](trait.Rng.html) that panics instead can be
+/// retrieved via [`into_panicking`](trait.TryRng.html#method.into_panicking).
+///
+/// Cloning forces the clone to reseed before its first output, so that it does
+/// not repeat the original's sequence.
+///
+/// Both guarantees rely on `S` being a source of entropy, such as
+/// [`OsRng`](struct.OsRng.html). A deterministic reseeder is copied along with
+/// the rest of the state, so forked children and clones would reseed from
+/// identical copies of it and still produce the same sequence.
+#[derive(Debug)]
+pub struct ReseedingRng<R, S> {
+    rng: R,
+    reseeder: S,
+    threshold: u64,
+    bytes_until_reseed: u64,
+    fork_generation: usize,
+}
+
+impl<R: SeedableRng + Rng, S: TryRng> ReseedingRng<R, S> {
+    /// Creates a new instance that wraps `rng` and reseeds it from `reseeder`
+    /// after every `threshold` bytes of output.
+    ///
+    /// A `threshold` of 0 disables reseeding based on output, leaving only
+    /// reseeding after a fork.
+    #[inline]
+    pub fn new(rng: R, threshold: u64, reseeder: S) -> Self {
+        fork::register();
+        ReseedingRng {
+            rng,
+            reseeder,
+            threshold,
+            bytes_until_reseed: threshold,
+            fork_generation: fork::generation(),
+        }
+    }
+
+    /// Creates a new instance whose initial state is seeded from `reseeder`,
+    /// returning an error if `reseeder` fails.
+    #[inline]
+    pub fn try_from_reseeder(threshold: u64, mut reseeder: S) -> Result<Self, S::Error> {
+        let rng = R::try_from_rng(&mut reseeder)?;
+        Ok(Self::new(rng, threshold, reseeder))
+    }
+
+    /// Immediately reseeds the wrapped generator, returning an error if the
+    /// reseeder fails.
+    pub fn try_reseed(&mut self) -> Result<(), S::Error> {
+        // Read the generation first so that a fork during reseeding is caught
+        // by the next call
+        let generation = fork::generation();
+        self.rng = R::try_from_rng(&mut self.reseeder)?;
+        self.bytes_until_reseed = self.threshold;
+        self.fork_generation = generation;
+        Ok(())
+    }
+
+    /// Reseeds if necessary before generating `bytes` bytes of output.
+    #[inline]
+    fn prepare(&mut self, bytes: usize) -> Result<&mut R, S::Error> {
+        let bytes = bytes as u64;
+        let forked = self.fork_generation != fork::generation();
+        let exhausted = self.threshold != 0 && bytes > self.bytes_until_reseed;
+        if forked || exhausted {
+            self.try_reseed()?;
+        }
+        self.bytes_until_reseed = self.bytes_until_reseed.saturating_sub(bytes);
+        Ok(&mut self.rng)
+    }
+
+    /// Returns a shared reference to the wrapped generator.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.rng
+    }
+
+    /// Returns a shared reference to the reseeder.
+    #[inline]
+    pub fn reseeder(&self) -> &S {
+        &self.reseeder
+    }
+
+    /// Returns the number of bytes of output between reseeds.
+    #[inline]
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+}
+
+impl<R: Clone, S: Clone> Clone for ReseedingRng<R, S> {
+    fn clone(&self) -> Self {
+        ReseedingRng {
+            rng: self.rng.clone(),
+            reseeder: self.reseeder.clone(),
+            threshold: self.threshold,
+            bytes_until_reseed: 0,
+            // A stale generation triggers a reseed even if `threshold` is 0
+            fork_generation: fork::generation().wrapping_sub(1),
+        }
+    }
+}
+
+impl<R: SeedableRng + Rng, S: TryRng> TryRng for ReseedingRng<R, S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), S::Error> {
+        self.prepare(buf.len())?.fill_bytes(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn try_next_u8(&mut self) -> Result<u8, S::Error> {
+        Ok(self.prepare(1)?.next_u8())
+    }
+
+    #[inline]
+    fn try_next_u16(&mut self) -> Result<u16, S::Error> {
+        Ok(self.prepare(2)?.next_u16())
+    }
+
+    #[inline]
+    fn try_next_u32(&mut self) -> Result<u32, S::Error> {
+        Ok(self.prepare(4)?.next_u32())
+    }
+
+    #[inline]
+    fn try_next_u64(&mut self) -> Result<u64, S::Error> {
+        Ok(self.prepare(8)?.next_u64())
+    }
+
+    #[inline]
+    fn try_next_u128(&mut self) -> Result<u128, S::Error> {
+        Ok(self.prepare(16)?.next_u128())
+    }
+}
+
+impl<R: CryptoRng, S: CryptoRng> CryptoRng for ReseedingRng<R, S> {}
+
+// The tests reseed from `OsRng`, as real users should
+#[cfg(all(test, any(target_os = "linux", target_vendor = "apple", all(unix, feature = "std"))))]
+mod tests {
+    use super::*;
+    use crate::{rng::{OsRng, xoshiro::Xoshiro256StarStar}, test_util};
+
+    fn next<S: TryRng>(rng: &mut ReseedingRng<Xoshiro256StarStar, S>) -> u64 {
+        rng.try_next_u64().ok().unwrap()
+    }
+
+    #[test]
+    fn reseeds_after_threshold() {
+        let mut rng = ReseedingRng::new(test_util::rng(1), 16, test_util::rng(2));
+        let mut plain = test_util::rng(1);
+        assert_eq!(next(&mut rng), plain.next_u64());
+        assert_eq!(next(&mut rng), plain.next_u64());
+        assert_ne!(next(&mut rng), plain.next_u64());
+    }
+
+    #[test]
+    fn clones_diverge() {
+        for &threshold in &[0, 1 << 20] {
+            let mut rng = ReseedingRng::new(test_util::rng(1), threshold, OsRng::new());
+            let mut a = rng.clone();
+            let mut b = rng.clone();
+            let outputs = [next(&mut rng), next(&mut a), next(&mut b)];
+            assert_ne!(outputs[0], outputs[1]);
+            assert_ne!(outputs[0], outputs[2]);
+            assert_ne!(outputs[1], outputs[2]);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn forked_children_diverge() {
+        extern "C" {
+            fn fork() -> i32;
+            fn pipe(fds: *mut i32) -> i32;
+            fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
+            fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+            fn close(fd: i32) -> i32;
+            fn waitpid(pid: i32, status: *mut i32, options: i32) -> i32;
+            fn _exit(status: i32) -> !;
+        }
+
+        let mut rng = ReseedingRng::new(test_util::rng(1), 0, OsRng::new());
+
+        // Forks a child that sends its first output back through a pipe
+        let mut spawn = || {
+            let mut fds = [0; 2];
+            assert_eq!(unsafe { pipe(fds.as_mut_ptr()) }, 0);
+            let pid = unsafe { fork() };
+            assert!(pid >= 0);
+            if pid == 0 {
+                let bytes = next(&mut rng).to_ne_bytes();
+                unsafe {
+                    // Safe because the buffer is valid for its length, and the
+                    // child exits without running the test harness's
+                    // destructors
+                    write(fds[1], bytes.as_ptr(), bytes.len());
+                    _exit(0);
+                }
+            }
+            unsafe {
+                // Safe because `fds[1]` is open and only the child writes to it
+                close(fds[1]);
+            }
+            (pid, fds[0])
+        };
+        let children = [spawn(), spawn()];
+
+        let mut outputs = [0; 3];
+        for (output, &(pid, fd)) in outputs.iter_mut().zip(&children) {
+            let mut bytes = [0; 8];
+            let mut status = 0;
+            unsafe {
+                // Safe because the buffer and status are valid for writes
+                assert_eq!(read(fd, bytes.as_mut_ptr(), bytes.len()), 8);
+                close(fd);
+                assert_eq!(waitpid(pid, &mut status, 0), pid);
+            }
+            assert_eq!(status, 0);
+            *output = u64::from_ne_bytes(bytes);
+        }
+        outputs[2] = next(&mut rng);
+
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(outputs[0], outputs[2]);
+        assert_ne!(outputs[1], outputs[2]);
+    }
+}
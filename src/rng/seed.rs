@@ -0,0 +1,48 @@
+use crate::{prelude::*, rng::splitmix::SplitMix64};
+
+/// A random number generator that can be explicitly seeded to produce a
+/// reproducible sequence of numbers.
+pub trait SeedableRng: Sized {
+    /// The seed type used to create `Self`.
+    ///
+    /// This is typically a byte array, such as `[u8; 32]`.
+    type Seed: Sized + Default + AsMut<[u8]>;
+
+    /// Creates a new instance from `seed`.
+    ///
+    /// Equal seeds must produce equal sequences of numbers.
+    fn from_seed(seed: Self::Seed) -> Self;
+
+    /// Creates a new instance from a 64-bit integer by expanding `state` with
+    /// SplitMix64.
+    ///
+    /// This is convenient for reproducible tests and simulations, but is not
+    /// suitable for cryptographic use since only 64 bits of the seed vary.
+    fn seed_from_u64(state: u64) -> Self {
+        let mut rng = SplitMix64::new(state);
+        let mut seed = Self::Seed::default();
+        for chunk in seed.as_mut().chunks_mut(8) {
+            let bytes = rng.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Self::from_seed(seed)
+    }
+
+    /// Creates a new instance seeded from `rng` without fail.
+    #[inline]
+    fn from_rng<R: ?Sized + Rng>(rng: &mut R) -> Self {
+        match Self::try_from_rng(rng) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Creates a new instance seeded from `rng`, returning an error if `rng`
+    /// fails.
+    #[inline]
+    fn try_from_rng<R: ?Sized + TryRng>(rng: &mut R) -> Result<Self, R::Error> {
+        let mut seed = Self::Seed::default();
+        rng.try_fill_bytes(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
+    }
+}
@@ -0,0 +1,71 @@
+//! A lazily seeded random number generator local to each thread.
+
+use std::{cell::UnsafeCell, rc::Rc};
+use crate::{prelude::*, rng::{OsRng, ReseedingRng, chacha::ChaCha12}};
+
+/// The number of bytes generated before reseeding from the operating system.
+const RESEED_THRESHOLD: u64 = 64 * 1024;
+
+type Core = ReseedingRng<ChaCha12, OsRng>;
+
+thread_local! {
+    static THREAD_RNG: Rc<UnsafeCell<Core>> = {
+        match Core::try_from_reseeder(RESEED_THRESHOLD, OsRng::new()) {
+            Ok(core) => Rc::new(UnsafeCell::new(core)),
+            Err(err) => panic!("could not seed thread-local generator: {:?}", err),
+        }
+    };
+}
+
+/// A handle to the cryptographically secure random number generator local to
+/// the current thread, retrieved via [`thread_rng`](fn.thread_rng.html).
+///
+/// The generator is seeded from [`OsRng`](struct.OsRng.html) on first use, and
+/// is reseeded after every 64 KiB of output or after the process is forked.
+/// Generating numbers panics if reseeding fails.
+///
+/// This type is not `Send` or `Sync`, so the state of one thread can never be
+/// used by another.
+#[derive(Clone, Debug)]
+pub struct ThreadRng {
+    core: Rc<UnsafeCell<Core>>,
+}
+
+/// Returns a handle to the random number generator local to the current thread.
+///
+/// # Panics
+///
+/// Panics if called while the current thread is being destroyed, or if the
+/// operating system fails to provide a seed.
+#[inline]
+pub fn thread_rng() -> ThreadRng {
+    ThreadRng { core: THREAD_RNG.with(Rc::clone) }
+}
+
+impl ThreadRng {
+    #[inline]
+    fn core(&mut self) -> &mut Core {
+        // Safe because `ThreadRng` is neither `Send` nor `Sync`, and no method
+        // of `Core` can call back into another handle
+        unsafe { &mut *self.core.get() }
+    }
+}
+
+impl Rng for ThreadRng {
+    #[inline]
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        self.core().as_panicking().fill_bytes(buf);
+    }
+
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.core().as_panicking().next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.core().as_panicking().next_u64()
+    }
+}
+
+impl CryptoRng for ThreadRng {}
@@ -0,0 +1,225 @@
+//! Xoshiro random number generators.
+//!
+//! These are fast, non-cryptographic generators with 256 bits of state,
+//! designed by David Blackman and Sebastiano Vigna. See
+//! [here](http://prng.di.unimi.it) for details.
+
+use crate::prelude::*;
+
+/// The polynomial used by `jump` to advance the state by 2<sup>128</sup>
+/// calls.
+const JUMP: [u64; 4] = [
+    0x180E_C6D3_3CFD_0ABA,
+    0xD5A6_1266_F0C9_392C,
+    0xA958_2618_E03F_C9AA,
+    0x39AB_DC45_29B1_661C,
+];
+
+/// The polynomial used by `long_jump` to advance the state by
+/// 2<sup>192</sup> calls.
+const LONG_JUMP: [u64; 4] = [
+    0x76E1_5D3E_FEFD_CBBF,
+    0xC500_4E44_1C52_2FB3,
+    0x7771_0069_854E_E241,
+    0x3910_9BB0_2ACB_E635,
+];
+
+macro_rules! imp {
+    ($($(#[$meta:meta])+ $t:ident, |$s:ident| $scramble:expr;)+) => { $(
+        $(#[$meta])+
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $t {
+            s: [u64; 4],
+        }
+
+        impl $t {
+            /// Creates a new instance from `state`, or returns `None` if
+            /// `state` is all zeros.
+            #[inline]
+            pub const fn from_state(state: [u64; 4]) -> Option<Self> {
+                if state[0] == 0 && state[1] == 0 && state[2] == 0 && state[3] == 0 {
+                    None
+                } else {
+                    Some($t { s: state })
+                }
+            }
+
+            /// Returns the current state of `self`.
+            #[inline]
+            pub const fn state(&self) -> [u64; 4] {
+                self.s
+            }
+
+            /// Advances `self` by 2<sup>128</sup> calls to `next_u64`.
+            ///
+            /// This can be used to generate 2<sup>128</sup> non-overlapping
+            /// subsequences for parallel computations.
+            #[inline]
+            pub fn jump(&mut self) {
+                self.apply_jump(&JUMP);
+            }
+
+            /// Advances `self` by 2<sup>192</sup> calls to `next_u64`.
+            ///
+            /// This can be used to generate 2<sup>64</sup> starting points,
+            /// from each of which `jump` will generate 2<sup>64</sup>
+            /// non-overlapping subsequences for parallel distributed
+            /// computations.
+            #[inline]
+            pub fn long_jump(&mut self) {
+                self.apply_jump(&LONG_JUMP);
+            }
+
+            fn apply_jump(&mut self, poly: &[u64; 4]) {
+                let mut s = [0u64; 4];
+                for &word in poly {
+                    for bit in 0..64 {
+                        if word & (1 << bit) != 0 {
+                            for (a, b) in s.iter_mut().zip(&self.s) {
+                                *a ^= b;
+                            }
+                        }
+                        self.step();
+                    }
+                }
+                self.s = s;
+            }
+
+            #[inline]
+            fn step(&mut self) {
+                let s = &mut self.s;
+                let t = s[1] << 17;
+                s[2] ^= s[0];
+                s[3] ^= s[1];
+                s[1] ^= s[2];
+                s[0] ^= s[3];
+                s[2] ^= t;
+                s[3] = s[3].rotate_left(45);
+            }
+        }
+
+        impl Rng for $t {
+            #[inline]
+            fn fill_bytes(&mut self, buf: &mut [u8]) {
+                crate::rng::fill_bytes_via_next(self, buf);
+            }
+
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                // The upper bits are of higher quality
+                (self.next_u64() >> 32) as u32
+            }
+
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                let $s = &self.s;
+                let result = $scramble;
+                self.step();
+                result
+            }
+        }
+
+        impl SeedableRng for $t {
+            type Seed = [u8; 32];
+
+            /// Creates a new instance from `seed`.
+            ///
+            /// Because an all-zero state is invalid, an all-zero `seed` is
+            /// replaced with `seed_from_u64(0)`.
+            #[inline]
+            fn from_seed(seed: [u8; 32]) -> Self {
+                let mut s = [0u64; 4];
+                for (word, chunk) in s.iter_mut().zip(seed.chunks_exact(8)) {
+                    let mut bytes = [0; 8];
+                    bytes.copy_from_slice(chunk);
+                    *word = u64::from_le_bytes(bytes);
+                }
+                match Self::from_state(s) {
+                    Some(rng) => rng,
+                    None => Self::seed_from_u64(0),
+                }
+            }
+        }
+    )+ }
+}
+
+imp! {
+    /// The xoshiro256** generator.
+    ///
+    /// This is a solid all-purpose generator whose output passes all known
+    /// statistical tests.
+    Xoshiro256StarStar, |s| s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+
+    /// The xoshiro256++ generator.
+    ///
+    /// This is a solid all-purpose generator whose output passes all known
+    /// statistical tests. It is usually slightly faster than
+    /// [`Xoshiro256StarStar`](struct.Xoshiro256StarStar.html).
+    Xoshiro256PlusPlus, |s| s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Outputs of the reference C implementations from the state `[1, 2, 3, 4]`
+
+    #[test]
+    fn star_star_reference() {
+        let mut rng = Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap();
+        let expected = [
+            11520,
+            0,
+            1509978240,
+            1215971899390074240,
+            1216172134540287360,
+            607988272756665600,
+        ];
+        for &value in &expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn plus_plus_reference() {
+        let mut rng = Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+        let expected = [
+            41943041,
+            58720359,
+            3588806011781223,
+            3591011842654386,
+            9228616714210784205,
+            9973669472204895162,
+        ];
+        for &value in &expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn jump_reference() {
+        let mut rng = Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap();
+        rng.jump();
+        assert_eq!(rng.state(), [
+            0x8c7a153956b5f3d1,
+            0x701f1a713401d85e,
+            0x6527f66a65469085,
+            0x8386b786c4408050,
+        ]);
+
+        let mut rng = Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+        rng.long_jump();
+        assert_eq!(rng.state(), [
+            0x096a8eb71295a400,
+            0xdbf84991e50f4516,
+            0x534ee745810d2a0e,
+            0x31655ca1a2215bf1,
+        ]);
+    }
+
+    #[test]
+    fn zero_state_is_rejected() {
+        assert!(Xoshiro256StarStar::from_state([0; 4]).is_none());
+        assert!(Xoshiro256PlusPlus::from_state([0; 4]).is_none());
+    }
+}
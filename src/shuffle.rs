@@ -20,13 +20,51 @@ impl<A> Shuffle for [A] {
 
     #[inline]
     fn try_shuffle<R: ?Sized + TryRng>(&mut self, rng: &mut R) -> Result<(), R::Error> {
-        for i in 0..self.len() {
+        // Fisher-Yates (Durstenfeld) shuffle: each element is swapped with one
+        // chosen from the elements that have not yet been placed
+        for i in (1..self.len()).rev() {
             let j = unsafe {
-                // Safe because it will always return a value within the range
-                usize::try_rand_in_unchecked(rng, 0..self.len())?
+                // Safe because `0..=i` is never empty
+                usize::try_rand_in_unchecked(rng, 0..=i)?
             };
             self.swap(i, j);
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, test_util::{assert_chi_squared, rng}};
+
+    #[test]
+    fn every_permutation_is_equally_likely() {
+        const PERMUTATIONS: usize = 24;
+
+        let mut rng = rng(1);
+        let mut counts = [0u64; PERMUTATIONS];
+        for _ in 0..PERMUTATIONS * 20_000 {
+            let mut values = [0u8, 1, 2, 3];
+            values.shuffle(&mut rng);
+
+            // Rank the permutation in the factorial number system
+            let mut rank = 0;
+            for i in 0..values.len() {
+                let smaller = values[i + 1..].iter().filter(|&&v| v < values[i]).count();
+                rank = rank * (values.len() - i) + smaller;
+            }
+            counts[rank] += 1;
+        }
+        assert_chi_squared(&counts, &[1.0; PERMUTATIONS]);
+    }
+
+    #[test]
+    fn short_slices_are_unchanged() {
+        let mut rng = rng(2);
+        let mut empty: [u8; 0] = [];
+        empty.shuffle(&mut rng);
+        let mut one = [7];
+        one.shuffle(&mut rng);
+        assert_eq!(one, [7]);
+    }
+}
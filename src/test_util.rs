@@ -0,0 +1,67 @@
+//! Helpers shared by the statistical tests.
+
+use crate::rng::{SeedableRng, xoshiro::Xoshiro256StarStar};
+
+/// Returns a deterministic generator for tests.
+pub(crate) fn rng(seed: u64) -> Xoshiro256StarStar {
+    Xoshiro256StarStar::seed_from_u64(seed)
+}
+
+/// Asserts that `counts` are consistent with `expected` probabilities using
+/// Pearson's chi-squared test.
+///
+/// The bound is 5 standard deviations above the mean of the statistic, so a
+/// fixed seed never fails by chance while any real bias in a test with enough
+/// samples does.
+pub(crate) fn assert_chi_squared(counts: &[u64], expected: &[f64]) {
+    assert_eq!(counts.len(), expected.len());
+    let n: u64 = counts.iter().sum();
+    let total: f64 = expected.iter().sum();
+
+    let mut statistic = 0.0;
+    let mut dof = 0;
+    for (&count, &p) in counts.iter().zip(expected) {
+        if p == 0.0 {
+            assert_eq!(count, 0, "sampled a value with zero probability");
+            continue;
+        }
+        let e = n as f64 * p / total;
+        statistic += (count as f64 - e).powi(2) / e;
+        dof += 1;
+    }
+    let dof = (dof - 1) as f64;
+    let bound = dof + 5.0 * (2.0 * dof).sqrt();
+    assert!(statistic < bound, "chi-squared {} exceeds {} with {} degrees of freedom", statistic, bound, dof);
+}
+
+/// Asserts that the mean and variance of `samples` are within 5 standard
+/// errors of `mean` and `variance`, where `kurtosis` is the fourth standardized
+/// moment of the distribution, used to estimate the error of the variance.
+#[cfg(feature = "std")]
+pub(crate) fn assert_moments(samples: &[f64], mean: f64, variance: f64, kurtosis: f64) {
+    let n = samples.len() as f64;
+    let sample_mean = samples.iter().sum::<f64>() / n;
+    let sample_variance = samples.iter().map(|x| (x - sample_mean).powi(2)).sum::<f64>() / n;
+
+    let mean_error = (variance / n).sqrt();
+    let variance_error = variance * ((kurtosis - 1.0) / n).sqrt();
+    assert!((sample_mean - mean).abs() < 5.0 * mean_error, "mean {} is not close to {}", sample_mean, mean);
+    assert!((sample_variance - variance).abs() < 5.0 * variance_error, "variance {} is not close to {}", sample_variance, variance);
+}
+
+/// Asserts that `samples` are consistent with the cumulative distribution
+/// function `cdf` using the Kolmogorov–Smirnov test.
+///
+/// The bound of `2 / sqrt(n)` corresponds to a significance level below 0.001.
+#[cfg(feature = "std")]
+pub(crate) fn assert_ks(samples: &mut [f64], cdf: impl Fn(f64) -> f64) {
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = samples.len() as f64;
+    let mut d: f64 = 0.0;
+    for (i, &x) in samples.iter().enumerate() {
+        let c = cdf(x);
+        d = d.max((i as f64 + 1.0) / n - c).max(c - i as f64 / n);
+    }
+    let bound = 2.0 / n.sqrt();
+    assert!(d < bound, "Kolmogorov–Smirnov statistic {} exceeds {}", d, bound);
+}